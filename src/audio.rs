@@ -1,7 +1,9 @@
 use bevy::prelude::*;
 use bevy_seedling::{
     SeedlingPlugin,
+    prelude::LowPassNode,
     sample::{AudioSample, SamplePlayer},
+    sample_effects,
 };
 
 pub(super) fn plugin(app: &mut App) {
@@ -21,8 +23,14 @@ pub(super) fn plugin(app: &mut App) {
 pub struct Music;
 
 /// A music audio instance.
+///
+/// Carries a wide-open low-pass so effects like the fever slow-mo can muffle it.
 pub fn music(handle: Handle<AudioSample>) -> impl Bundle {
-    (SamplePlayer::new(handle).looping(), Music)
+    (
+        SamplePlayer::new(handle).looping(),
+        sample_effects![LowPassNode { frequency: 20_000.0 }],
+        Music,
+    )
 }
 
 /// An organizational marker component that should be added to a spawned [`AudioSample`] if it's in the
@@ -0,0 +1,191 @@
+//! Leaderboard local del modo supervivencia
+//!
+//! Las mejores `LEADERBOARD_SIZE` partidas se guardan en RON en la carpeta de
+//! datos del usuario (`$XDG_DATA_HOME`, `~/Library/Application Support` o
+//! `%APPDATA%`, según el sistema). Se lee al arrancar y se escribe cada vez
+//! que entra una marca. Si no hay carpeta (en la web, por ejemplo), solo
+//! dura lo que dure el juego abierto.
+
+use std::path::PathBuf;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Marcas que se guardan
+pub const LEADERBOARD_SIZE: usize = 10;
+/// Carpeta del juego dentro de la de datos del usuario
+const APP_DIR: &str = "bevy-jam-7";
+const LEADERBOARD_FILE: &str = "leaderboard.ron";
+
+pub(super) fn plugin(app: &mut App) {
+    app.insert_resource(Leaderboard::load());
+}
+
+#[derive(Resource, Serialize, Deserialize, Default, Clone, Debug)]
+pub struct Leaderboard {
+    /// De mejor a peor
+    pub entries: Vec<LeaderboardEntry>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct LeaderboardEntry {
+    pub name: String,
+    pub score: u32,
+    pub wave: i32,
+    pub kills: u32,
+    /// Segundos vivo
+    pub time: f32,
+}
+
+impl Leaderboard {
+    /// Si una partida con estos puntos entraría en el top
+    pub fn qualifies(&self, score: u32) -> bool {
+        self.entries.len() < LEADERBOARD_SIZE || self.entries.iter().any(|entry| score > entry.score)
+    }
+
+    /// Mete una marca en su sitio y guarda. Devuelve el puesto (desde 0) si entra.
+    pub fn submit(&mut self, entry: LeaderboardEntry) -> Option<usize> {
+        let rank = self.insert(entry)?;
+        self.save();
+        Some(rank)
+    }
+
+    /// Como `submit`, sin tocar el disco
+    fn insert(&mut self, entry: LeaderboardEntry) -> Option<usize> {
+        // A igual puntuación, la más antigua queda delante
+        let rank = self.entries.iter().position(|other| entry.score > other.score).unwrap_or(self.entries.len());
+        if rank >= LEADERBOARD_SIZE {
+            return None;
+        }
+        info!("🏆 '{}' entra {}º con {} puntos", entry.name, rank + 1, entry.score);
+        self.entries.insert(rank, entry);
+        self.entries.truncate(LEADERBOARD_SIZE);
+        Some(rank)
+    }
+
+    fn load() -> Self {
+        let Some(path) = leaderboard_path() else {
+            info!("Sin carpeta de datos: el leaderboard no se guarda");
+            return Self::default();
+        };
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Self::default(),
+            Err(err) => {
+                warn!("⚠️ No se pudo leer el leaderboard ({}): {err}", path.display());
+                return Self::default();
+            }
+        };
+        match ron::from_str::<Self>(&text) {
+            Ok(mut leaderboard) => {
+                leaderboard.entries.sort_by(|a, b| b.score.cmp(&a.score));
+                leaderboard.entries.truncate(LEADERBOARD_SIZE);
+                leaderboard
+            }
+            Err(err) => {
+                warn!("⚠️ Leaderboard mal escrito ({}), se empieza de cero: {err}", path.display());
+                Self::default()
+            }
+        }
+    }
+
+    fn save(&self) {
+        let Some(path) = leaderboard_path() else { return; };
+        let text = match ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default()) {
+            Ok(text) => text,
+            Err(err) => {
+                error!("No se pudo escribir el leaderboard: {err}");
+                return;
+            }
+        };
+        let written = match path.parent() {
+            Some(dir) => std::fs::create_dir_all(dir).and_then(|_| std::fs::write(&path, text)),
+            None => std::fs::write(&path, text),
+        };
+        if let Err(err) = written {
+            warn!("⚠️ No se pudo guardar el leaderboard ({}): {err}", path.display());
+        }
+    }
+}
+
+fn leaderboard_path() -> Option<PathBuf> {
+    Some(user_data_dir()?.join(APP_DIR).join(LEADERBOARD_FILE))
+}
+
+/// Carpeta de datos del usuario según el sistema
+fn user_data_dir() -> Option<PathBuf> {
+    let var = |key: &str| std::env::var_os(key).filter(|value| !value.is_empty()).map(PathBuf::from);
+    if cfg!(target_family = "wasm") {
+        None
+    } else if cfg!(target_os = "windows") {
+        var("APPDATA")
+    } else if cfg!(target_os = "macos") {
+        var("HOME").map(|home| home.join("Library/Application Support"))
+    } else {
+        var("XDG_DATA_HOME").or_else(|| var("HOME").map(|home| home.join(".local/share")))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(name: &str, score: u32) -> LeaderboardEntry {
+        LeaderboardEntry { name: name.to_string(), score, wave: 1, kills: 0, time: 0.0 }
+    }
+
+    fn names(leaderboard: &Leaderboard) -> Vec<&str> {
+        leaderboard.entries.iter().map(|e| e.name.as_str()).collect()
+    }
+
+    /// Top lleno: 1000, 900, ..., 100
+    fn full() -> Leaderboard {
+        let mut leaderboard = Leaderboard::default();
+        for i in 0..LEADERBOARD_SIZE as u32 {
+            leaderboard.insert(entry(&format!("p{i}"), 1000 - i * 100));
+        }
+        leaderboard
+    }
+
+    #[test]
+    fn ranks_by_score() {
+        let mut leaderboard = Leaderboard::default();
+        assert_eq!(leaderboard.insert(entry("b", 200)), Some(0));
+        assert_eq!(leaderboard.insert(entry("a", 300)), Some(0));
+        assert_eq!(leaderboard.insert(entry("c", 100)), Some(2));
+        assert_eq!(names(&leaderboard), ["a", "b", "c"]);
+    }
+
+    #[test]
+    fn ties_keep_the_older_score_first() {
+        let mut leaderboard = Leaderboard::default();
+        leaderboard.insert(entry("old", 500));
+        assert_eq!(leaderboard.insert(entry("new", 500)), Some(1));
+        assert_eq!(names(&leaderboard), ["old", "new"]);
+    }
+
+    #[test]
+    fn eleventh_entry_below_the_top_is_rejected() {
+        let mut leaderboard = full();
+        assert!(!leaderboard.qualifies(100));
+        assert_eq!(leaderboard.insert(entry("late", 100)), None);
+        assert_eq!(leaderboard.entries.len(), LEADERBOARD_SIZE);
+        assert!(leaderboard.entries.iter().all(|e| e.name != "late"));
+    }
+
+    #[test]
+    fn eleventh_entry_in_the_top_drops_the_last() {
+        let mut leaderboard = full();
+        assert!(leaderboard.qualifies(550));
+        assert_eq!(leaderboard.insert(entry("mid", 550)), Some(5));
+        assert_eq!(leaderboard.entries.len(), LEADERBOARD_SIZE);
+        assert_eq!(leaderboard.entries.last().unwrap().score, 200);
+    }
+
+    #[test]
+    fn anything_qualifies_while_not_full() {
+        let mut leaderboard = Leaderboard::default();
+        leaderboard.insert(entry("a", 1000));
+        assert!(leaderboard.qualifies(1));
+    }
+}
@@ -7,6 +7,7 @@ mod asset_tracking;
 mod audio;
 #[cfg(feature = "dev")]
 mod dev_tools;
+mod leaderboard;
 mod menus;
 mod screens;
 mod theme;
@@ -18,6 +19,10 @@ use bevy_skein::SkeinPlugin;
 use bevy::render::{RenderPlugin, settings::{RenderCreation, WgpuSettings, Backends}};
 
 fn main() -> AppExit {
+    #[cfg(feature = "dev")]
+    if let Some(enemies) = screens::enemy_bench::requested() {
+        return screens::enemy_bench::run(enemies);
+    }
     App::new().add_plugins(AppPlugin).run()
 }
 
@@ -56,6 +61,7 @@ impl Plugin for AppPlugin {
             audio::plugin,
             #[cfg(feature = "dev")]
             dev_tools::plugin,
+            leaderboard::plugin,
             menus::plugin,
             screens::plugin,
             theme::plugin,
@@ -5,7 +5,7 @@ use bevy::{prelude::*, window::CursorOptions};
 use crate::{
     asset_tracking::ResourceHandles,
     menus::Menu,
-    screens::{Screen, set_cursor_grab},
+    screens::{GameMode, Screen, set_cursor_grab},
     theme::widget,
 };
 
@@ -22,25 +22,37 @@ fn spawn_main_menu(mut commands: Commands, mut cursor_options: Single<&mut Curso
         DespawnOnExit(Menu::Main),
         #[cfg(not(target_family = "wasm"))]
         children![
-            widget::button("Play", enter_loading_or_gameplay_screen),
+            widget::button("Play", play_story),
+            widget::button("Survival", play_survival),
             widget::button("Settings", open_settings_menu),
             widget::button("Credits", open_credits_menu),
             widget::button("Exit", exit_app),
         ],
         #[cfg(target_family = "wasm")]
         children![
-            widget::button("Play", enter_loading_or_gameplay_screen),
+            widget::button("Play", play_story),
+            widget::button("Survival", play_survival),
             widget::button("Settings", open_settings_menu),
             widget::button("Credits", open_credits_menu),
         ],
     ));
 }
 
+fn play_story(_: On<Pointer<Click>>, mut commands: Commands) {
+    commands.run_system_cached_with(enter_loading_or_gameplay_screen, GameMode::Story);
+}
+
+fn play_survival(_: On<Pointer<Click>>, mut commands: Commands) {
+    commands.run_system_cached_with(enter_loading_or_gameplay_screen, GameMode::Survival);
+}
+
 fn enter_loading_or_gameplay_screen(
-    _: On<Pointer<Click>>,
+    In(mode): In<GameMode>,
     resource_handles: Res<ResourceHandles>,
+    mut game_mode: ResMut<GameMode>,
     mut next_screen: ResMut<NextState<Screen>>,
 ) {
+    *game_mode = mode;
     if resource_handles.is_all_done() {
         next_screen.set(Screen::Gameplay);
     } else {
@@ -5,10 +5,13 @@ mod death;
 mod main;
 mod pause;
 mod settings;
+mod survival;
 mod victory;
 
 use bevy::prelude::*;
 
+pub use settings::Difficulty;
+
 pub(super) fn plugin(app: &mut App) {
     app.init_state::<Menu>();
 
@@ -19,6 +22,7 @@ pub(super) fn plugin(app: &mut App) {
         pause::plugin,
         death::plugin,
         victory::plugin,
+        survival::plugin,
     ));
 }
 
@@ -32,4 +36,6 @@ pub enum Menu {
     Pause,
     Death,
     Victory,
+    /// End of a survival run: score, name entry and leaderboard
+    SurvivalOver,
 }
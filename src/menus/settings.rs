@@ -7,11 +7,17 @@ use bevy::{input::common_conditions::input_just_pressed, prelude::*};
 use crate::{menus::Menu, screens::Screen, theme::prelude::*};
 
 pub(super) fn plugin(app: &mut App) {
+    app.register_type::<Difficulty>();
+    app.init_resource::<Difficulty>();
     app.add_systems(OnEnter(Menu::Settings), spawn_settings_menu);
     app.add_systems(
         Update,
         go_back.run_if(in_state(Menu::Settings).and(input_just_pressed(KeyCode::Escape))),
     );
+    app.add_systems(
+        Update,
+        update_difficulty_label.run_if(in_state(Menu::Settings)),
+    );
 
     // app.add_systems(
     //     Update,
@@ -51,6 +57,14 @@ fn settings_grid() -> impl Bundle {
                 }
             ),
             global_volume_widget(),
+            (
+                widget::label("Difficulty"),
+                Node {
+                    justify_self: JustifySelf::End,
+                    ..default()
+                }
+            ),
+            difficulty_widget(),
         ],
     )
 }
@@ -78,6 +92,74 @@ fn global_volume_widget() -> impl Bundle {
     )
 }
 
+fn difficulty_widget() -> impl Bundle {
+    (
+        Name::new("Difficulty Widget"),
+        Node {
+            justify_self: JustifySelf::Start,
+            ..default()
+        },
+        children![
+            widget::button_small("-", lower_difficulty),
+            (
+                Name::new("Current Difficulty"),
+                Node {
+                    padding: UiRect::horizontal(px(10)),
+                    justify_content: JustifyContent::Center,
+                    ..default()
+                },
+                children![(widget::label(""), DifficultyLabel)],
+            ),
+            widget::button_small("+", raise_difficulty),
+        ],
+    )
+}
+
+/// Dificultad de los combates: cuántos enemigos pueden atacar a la vez, etc.
+#[derive(Resource, Reflect, Clone, Copy, PartialEq, Eq, Debug, Default)]
+#[reflect(Resource)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+}
+
+impl Difficulty {
+    fn label(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+        }
+    }
+}
+
+fn lower_difficulty(_: On<Pointer<Click>>, mut difficulty: ResMut<Difficulty>) {
+    *difficulty = match *difficulty {
+        Difficulty::Hard => Difficulty::Normal,
+        _ => Difficulty::Easy,
+    };
+}
+
+fn raise_difficulty(_: On<Pointer<Click>>, mut difficulty: ResMut<Difficulty>) {
+    *difficulty = match *difficulty {
+        Difficulty::Easy => Difficulty::Normal,
+        _ => Difficulty::Hard,
+    };
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+struct DifficultyLabel;
+
+fn update_difficulty_label(
+    difficulty: Res<Difficulty>,
+    mut label: Single<&mut Text, With<DifficultyLabel>>,
+) {
+    label.0 = difficulty.label().to_string();
+}
+
 // const MIN_VOLUME: f32 = 0.0;
 // const MAX_VOLUME: f32 = 3.0;
 
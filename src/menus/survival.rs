@@ -0,0 +1,234 @@
+//! The end-of-run menu for survival mode: score, name entry and the local leaderboard.
+
+use bevy::{
+    input::{
+        ButtonState,
+        keyboard::{Key, KeyboardInput},
+    },
+    prelude::*,
+    window::CursorOptions,
+};
+
+use crate::{
+    leaderboard::{Leaderboard, LeaderboardEntry},
+    menus::Menu,
+    screens::{Screen, SurvivalRun, set_cursor_grab},
+    theme::{palette::*, widget},
+};
+
+const MAX_NAME_LEN: usize = 12;
+const DEFAULT_NAME: &str = "Anonymous";
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_resource::<NameEntry>();
+    app.add_systems(OnEnter(Menu::SurvivalOver), spawn_survival_over_menu);
+    app.add_systems(
+        Update,
+        (type_name, update_name_label, refresh_leaderboard)
+            .chain()
+            .run_if(in_state(Menu::SurvivalOver)),
+    );
+}
+
+/// The name being typed for this run's score.
+#[derive(Resource, Default)]
+struct NameEntry {
+    name: String,
+    /// Where the run landed once submitted (`None` if it didn't make the top).
+    rank: Option<usize>,
+    submitted: bool,
+}
+
+/// The name field and its save button; removed once the score is submitted.
+#[derive(Component)]
+struct NameEntryRow;
+
+#[derive(Component)]
+struct NameLabel;
+
+#[derive(Component)]
+struct LeaderboardList;
+
+fn spawn_survival_over_menu(
+    mut commands: Commands,
+    mut cursor_options: Single<&mut CursorOptions>,
+    run: Res<SurvivalRun>,
+    leaderboard: Res<Leaderboard>,
+    mut entry: ResMut<NameEntry>,
+) {
+    set_cursor_grab(&mut cursor_options, false);
+    *entry = NameEntry::default();
+    // Nothing to enter if the run can't make the board
+    entry.submitted = run.score == 0 || !leaderboard.qualifies(run.score);
+
+    let seconds = run.time as u32;
+    let summary = format!(
+        "{} points — wave {} — {} kills (best streak {}) — {}:{:02}",
+        run.score,
+        run.wave,
+        run.kills,
+        run.best_streak,
+        seconds / 60,
+        seconds % 60,
+    );
+
+    let root = commands
+        .spawn((
+            widget::ui_root("Survival Over Menu"),
+            GlobalZIndex(2),
+            DespawnOnExit(Menu::SurvivalOver),
+        ))
+        .id();
+    commands.entity(root).with_children(|parent| {
+        parent.spawn(widget::header("Game Over"));
+        parent.spawn(widget::label(summary));
+        if !entry.submitted {
+            parent.spawn((
+                Name::new("Name Entry"),
+                NameEntryRow,
+                Node {
+                    align_items: AlignItems::Center,
+                    column_gap: px(30),
+                    ..default()
+                },
+                children![
+                    (widget::label("Name: _"), NameLabel),
+                    widget::button("Save Score", save_score),
+                ],
+            ));
+        }
+        parent.spawn((
+            Name::new("Leaderboard"),
+            LeaderboardList,
+            Node {
+                display: Display::Grid,
+                row_gap: px(4),
+                column_gap: px(30),
+                grid_template_columns: vec![
+                    GridTrack::px(40.0),
+                    GridTrack::px(200.0),
+                    GridTrack::px(110.0),
+                    GridTrack::px(70.0),
+                    GridTrack::px(70.0),
+                ],
+                ..default()
+            },
+        ));
+        parent.spawn(widget::button("Play Again", play_again));
+        parent.spawn(widget::button("Quit to title", quit_to_title));
+    });
+}
+
+fn type_name(mut commands: Commands, mut input: MessageReader<KeyboardInput>, mut entry: ResMut<NameEntry>) {
+    for key in input.read() {
+        if entry.submitted || key.state != ButtonState::Pressed {
+            continue;
+        }
+        match &key.logical_key {
+            Key::Backspace => {
+                entry.name.pop();
+            }
+            Key::Enter => commands.run_system_cached(submit_score),
+            Key::Space => push_name_char(&mut entry.name, ' '),
+            Key::Character(text) => {
+                for c in text.chars() {
+                    push_name_char(&mut entry.name, c);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn push_name_char(name: &mut String, c: char) {
+    let allowed = c.is_alphanumeric() || matches!(c, ' ' | '-' | '_');
+    if allowed && name.chars().count() < MAX_NAME_LEN && !(c == ' ' && name.is_empty()) {
+        name.push(c);
+    }
+}
+
+fn update_name_label(entry: Res<NameEntry>, mut label: Query<&mut Text, With<NameLabel>>) {
+    if !entry.is_changed() {
+        return;
+    }
+    for mut text in &mut label {
+        text.0 = format!("Name: {}_", entry.name);
+    }
+}
+
+fn save_score(_: On<Pointer<Click>>, mut commands: Commands) {
+    commands.run_system_cached(submit_score);
+}
+
+fn submit_score(
+    mut commands: Commands,
+    mut entry: ResMut<NameEntry>,
+    run: Res<SurvivalRun>,
+    mut leaderboard: ResMut<Leaderboard>,
+    row: Query<Entity, With<NameEntryRow>>,
+) {
+    if entry.submitted {
+        return;
+    }
+    entry.submitted = true;
+    let name = entry.name.trim();
+    let name = if name.is_empty() { DEFAULT_NAME } else { name };
+    entry.rank = leaderboard.submit(LeaderboardEntry {
+        name: name.to_string(),
+        score: run.score,
+        wave: run.wave,
+        kills: run.kills,
+        time: run.time,
+    });
+    for entity in &row {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// Rebuilds the top-N grid when the menu opens and whenever a score is saved.
+fn refresh_leaderboard(
+    mut commands: Commands,
+    leaderboard: Res<Leaderboard>,
+    entry: Res<NameEntry>,
+    list: Single<(Entity, Ref<LeaderboardList>)>,
+) {
+    let (list, marker) = list.into_inner();
+    if !marker.is_added() && !leaderboard.is_changed() {
+        return;
+    }
+
+    commands.entity(list).despawn_children().with_children(|parent| {
+        for header in ["#", "Name", "Score", "Wave", "Time"] {
+            parent.spawn(leaderboard_cell(header, HEADER_TEXT));
+        }
+        for (i, record) in leaderboard.entries.iter().enumerate() {
+            let color = if entry.rank == Some(i) { BUTTON_HOVERED_BACKGROUND } else { LABEL_TEXT };
+            let seconds = record.time as u32;
+            parent.spawn(leaderboard_cell(format!("{}", i + 1), color));
+            parent.spawn(leaderboard_cell(record.name.clone(), color));
+            parent.spawn(leaderboard_cell(record.score.to_string(), color));
+            parent.spawn(leaderboard_cell(record.wave.to_string(), color));
+            parent.spawn(leaderboard_cell(format!("{}:{:02}", seconds / 60, seconds % 60), color));
+        }
+    });
+    if leaderboard.entries.is_empty() {
+        commands.entity(list).with_child(leaderboard_cell("No scores yet", LABEL_TEXT));
+    }
+}
+
+fn leaderboard_cell(text: impl Into<String>, color: Color) -> impl Bundle {
+    (
+        Name::new("Leaderboard Cell"),
+        Text(text.into()),
+        TextFont::from_font_size(18.0),
+        TextColor(color),
+    )
+}
+
+fn play_again(_: On<Pointer<Click>>, mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Gameplay);
+}
+
+fn quit_to_title(_: On<Pointer<Click>>, mut next_screen: ResMut<NextState<Screen>>) {
+    next_screen.set(Screen::Title);
+}
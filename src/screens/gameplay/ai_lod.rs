@@ -0,0 +1,144 @@
+//! Nivel de detalle de la IA
+//!
+//! Con cientos de enemigos no todos pueden pensar cada frame. Según la
+//! distancia al jugador:
+//!
+//! - `Full`: cerca, percepción y cerebro cada frame.
+//! - `Reduced`: a media distancia piensan unas pocas veces por segundo (con
+//!   el tiempo acumulado, así los temporizadores no se retrasan). Se siguen
+//!   moviendo cada frame con el último estado.
+//! - `Asleep`: lejos y sin nada que hacer, ni piensan ni se mueven ni echan
+//!   partículas.
+//!
+//! Un enemigo ocupado (cargando, atacando, aturdido...) siempre va en
+//! `Full`, y uno que persigue o busca nunca se duerme. Un golpe lo despierta
+//! ese mismo frame.
+
+use avian3d::prelude::*;
+use bevy::prelude::*;
+
+use crate::screens::Screen;
+use crate::screens::gameplay::{
+    enemy::Enemy,
+    enemy_ai::{EnemyBrain, EnemyState},
+    hitstop::MeleeImpact,
+    perception::{self, Perception},
+    player::Player,
+};
+
+pub struct AiLodPlugin;
+
+/// Hasta aquí piensan cada frame
+const FULL_RANGE: f32 = 30.0;
+/// Hasta aquí piensan a ratos; más lejos duermen
+const REDUCED_RANGE: f32 = 70.0;
+/// Margen para no cambiar de nivel cada frame en la frontera
+const HYSTERESIS: f32 = 5.0;
+/// Segundos entre pensamientos en `Reduced`
+const REDUCED_INTERVAL: f32 = 0.25;
+
+impl Plugin for AiLodPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (insert_ai_lod, update_ai_lod)
+                .chain()
+                .before(perception::update_sight)
+                .run_if(in_state(Screen::Gameplay)),
+        );
+    }
+}
+
+/// De más a menos detalle
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Default, Reflect)]
+pub enum LodLevel {
+    #[default]
+    Full,
+    Reduced,
+    Asleep,
+}
+
+#[derive(Component, Debug, Reflect)]
+pub struct AiLod {
+    pub level: LodLevel,
+    /// Si este frame le toca pensar
+    pub think: bool,
+    /// Tiempo desde la última vez que pensó (incluye este frame)
+    pub elapsed: f32,
+}
+
+impl AiLod {
+    /// Repartido por entidad para que no piensen todos en el mismo frame
+    fn new(entity: Entity) -> Self {
+        Self {
+            level: LodLevel::Full,
+            think: true,
+            elapsed: (entity.to_bits() % 16) as f32 / 16.0 * REDUCED_INTERVAL,
+        }
+    }
+}
+
+/// Lo que tiene que avanzar la IA este frame, o `None` si no le toca pensar.
+/// Sin `AiLod` (recién spawneado) piensa cada frame.
+pub fn think_dt(lod: Option<&AiLod>, frame_dt: f32) -> Option<f32> {
+    match lod {
+        Some(lod) if !lod.think => None,
+        Some(lod) => Some(lod.elapsed),
+        None => Some(frame_dt),
+    }
+}
+
+pub(super) fn insert_ai_lod(mut commands: Commands, enemies: Query<Entity, (With<Enemy>, Without<AiLod>)>) {
+    for entity in &enemies {
+        commands.entity(entity).insert(AiLod::new(entity));
+    }
+}
+
+pub(super) fn update_ai_lod(
+    time: Res<Time>,
+    mut impacts: MessageReader<MeleeImpact>,
+    mut enemies: Query<(Entity, &Transform, &EnemyBrain, &Perception, &mut AiLod, &mut LinearVelocity), With<Enemy>>,
+    player: Single<&Transform, With<Player>>,
+) {
+    let hits: Vec<Entity> = impacts.read().map(|i| i.enemy).collect();
+
+    for (entity, transform, brain, perception, mut lod, mut linear_velocity) in enemies.iter_mut() {
+        if lod.think {
+            lod.elapsed = 0.0;
+        }
+        lod.elapsed += time.delta_secs();
+
+        let distance = transform.translation.distance(player.translation);
+        // Para subir de nivel hay que pasar la frontera por el margen
+        let margin = |level: LodLevel| if lod.level == level { HYSTERESIS } else { 0.0 };
+        let by_distance = if distance <= FULL_RANGE + margin(LodLevel::Full) {
+            LodLevel::Full
+        } else if distance <= REDUCED_RANGE + margin(LodLevel::Reduced) {
+            LodLevel::Reduced
+        } else {
+            LodLevel::Asleep
+        };
+        let level = match brain.state {
+            EnemyState::Windup | EnemyState::Attack | EnemyState::Recover | EnemyState::Stunned => LodLevel::Full,
+            EnemyState::Alert | EnemyState::Chase | EnemyState::Search => by_distance.min(LodLevel::Reduced),
+            // Un ruido lo despierta para que el cerebro lo oiga
+            _ if perception.heard => by_distance.min(LodLevel::Reduced),
+            _ => by_distance,
+        };
+        let level = if hits.contains(&entity) { LodLevel::Full } else { level };
+
+        if level == LodLevel::Asleep && lod.level != LodLevel::Asleep {
+            linear_velocity.0 = Vec3::ZERO;
+        }
+        lod.level = level;
+        lod.think = match level {
+            LodLevel::Full => true,
+            LodLevel::Reduced => lod.elapsed >= REDUCED_INTERVAL,
+            LodLevel::Asleep => false,
+        };
+        // Lo que pasó mientras dormía no cuenta
+        if level == LodLevel::Asleep {
+            lod.elapsed = 0.0;
+        }
+    }
+}
@@ -1,23 +1,34 @@
 //! Sistema del reloj de alarma — arma especial que paraliza enemigos
 //!
 //! Flujo simplificado:
-//! Enemy muerte → drop reloj → jugador recoge (F) → jugador lanza (RMB) →
-//! vuela 0.5s → time field aparece y crece → enemigos paralizados → fade
+//! Enemy muerte → drop reloj (`loot`) → jugador lo agarra (F, vía `Grabbable`) →
+//! se guarda como carga (máx. `MAX_CLOCK_CHARGES`) → jugador lanza (RMB) →
+//! vuela 0.8s → time field aparece y crece → el tiempo se dilata según la
+//! distancia al centro → el campo se encoge y todo recupera su velocidad
 
 use bevy::prelude::*;
 use avian3d::prelude::*;
 use rand::RngExt;
 use crate::screens::Screen;
+use crate::PausableSystems;
 use crate::screens::gameplay::LevelAssets;
-use crate::screens::gameplay::enemy::Enemy;
+use crate::screens::gameplay::enemy::{Enemy, EnemyAnimationPlayer, EnemyAnimations};
 use crate::screens::gameplay::events::SpawnAlarmClockEvent;
+use crate::screens::gameplay::grab::{self, Grabbable, Held, PropThrown};
+use crate::screens::gameplay::particle_system::DarkParticle;
+use crate::screens::gameplay::player::Player;
 pub struct AlarmClockPlugin;
 
-const PICKUP_RANGE: f32 = 2.5;
+pub const MAX_CLOCK_CHARGES: u32 = 3;
 const THROW_SPEED: f32 = 18.0;
 const TIME_FIELD_RADIUS: f32 = 10.0;
 const TIME_FIELD_DURATION: f32 = 5.0;
-const FREEZE_DURATION: f32 = 5.0;
+/// Tiempo que tarda el campo en alcanzar su radio máximo
+const TIME_FIELD_GROW: f32 = 0.3;
+/// Últimos segundos del campo, en los que se encoge hasta desaparecer
+const TIME_FIELD_FADE: f32 = 1.2;
+/// Escala de tiempo en el centro exacto del campo
+const TIME_FIELD_MIN_SCALE: f32 = 0.05;
 const STAR_COUNT: usize = 12;
 
 impl Plugin for AlarmClockPlugin {
@@ -25,18 +36,20 @@ impl Plugin for AlarmClockPlugin {
         app.add_systems(
             Update,
             (
-                player_picks_alarm_clock,
-                player_throw_alarm_clock,
+                collect_clock_charge,
+                throw_clock_charge.before(grab::throw_held),
+                arm_thrown_clock,
                 alarm_clock_tick,
-            ).run_if(in_state(Screen::Gameplay)),
+            ).in_set(PausableSystems).run_if(in_state(Screen::Gameplay)),
         );
         app.add_systems(
             Update,
             (
                 time_field_tick,
-                frozen_enemy_tick,
+                apply_time_dilation,
+                dilate_enemy_animations,
                 star_tick,
-            ).run_if(in_state(Screen::Gameplay)),
+            ).chain().in_set(PausableSystems).run_if(in_state(Screen::Gameplay)),
         );
     }
 }
@@ -51,17 +64,33 @@ pub struct AlarmClock {
     pub is_thrown: bool,
 }
 
-#[derive(Component)]
-pub struct HeldClock;
-
 #[derive(Component)]
 pub struct TimeField {
     pub timer: f32,
 }
 
+/// Relojes que el jugador lleva encima
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct ClockCharges {
+    pub charges: u32,
+    pub max: u32,
+}
+
+impl Default for ClockCharges {
+    fn default() -> Self {
+        Self { charges: 0, max: MAX_CLOCK_CHARGES }
+    }
+}
+
+/// Escala de tiempo local dentro de un time field (1.0 = normal).
+/// Enemigos, partículas y cuerpos dinámicos la leen para ralentizarse.
 #[derive(Component)]
-pub struct FrozenEnemy {
-    pub remaining: f32,
+pub struct TimeDilation {
+    pub scale: f32,
+    /// Escala ya aplicada a la velocidad de un cuerpo dinámico
+    applied: f32,
+    base_gravity: f32,
 }
 
 #[derive(Component)]
@@ -78,92 +107,84 @@ pub fn spawn_alarm_clock(
     commands: &mut Commands,
     level_assets: &LevelAssets,
     position: Vec3,
-) {
+) -> Entity {
     commands.spawn((
         Name::new("AlarmClock"),
         AlarmClock { thrown_timer: 0.0, is_thrown: false },
+        Grabbable,
         SceneRoot(level_assets.alarm_clock_scene.clone()),
         Transform::from_translation(position),
         RigidBody::Dynamic,
         Collider::sphere(0.3),
         LinearVelocity::default(),
         AngularVelocity::default(),
-    ));
+    )).id()
 }
 
 // -----------------------------------------------
-// RECOGER
+// CARGAS — agarrar un reloj lo guarda si hay hueco
 // -----------------------------------------------
 
-fn player_picks_alarm_clock(
+fn collect_clock_charge(
+    mut commands: Commands,
+    clocks: Query<(Entity, &AlarmClock, &Held), Added<Held>>,
+    mut charges: Single<&mut ClockCharges, With<Player>>,
+) {
+    for (entity, clock, held) in clocks.iter() {
+        if clock.is_thrown || charges.charges >= charges.max {
+            // Lleno: se queda en la mano como cualquier otro objeto
+            continue;
+        }
+        grab::release(&mut commands, entity, held);
+        commands.entity(entity).despawn();
+        charges.charges += 1;
+        info!("Reloj guardado ({}/{})", charges.charges, charges.max);
+    }
+}
+
+fn throw_clock_charge(
     mut commands: Commands,
-    keyboard: Res<ButtonInput<KeyCode>>,
-    player: Single<&Transform, With<super::player::Player>>,
-    camera: Single<Entity, With<Camera3d>>,
-    mut clocks: Query<(Entity, &Transform, &mut AlarmClock), Without<HeldClock>>,
+    mouse: Res<ButtonInput<MouseButton>>,
+    camera: Single<&GlobalTransform, With<Camera3d>>,
+    mut charges: Single<&mut ClockCharges, With<Player>>,
+    held: Query<(), With<Held>>,
+    level_assets: Res<LevelAssets>,
 ) {
-    if !keyboard.just_pressed(KeyCode::KeyF) { return; }
-
-    let player_pos = player.translation;
-
-    for (entity, clock_transform, mut clock) in clocks.iter_mut() {
-        if clock.is_thrown { continue; }
-
-        let dist = player_pos.distance(clock_transform.translation);
-        if dist > PICKUP_RANGE { continue; }
-
-        commands.entity(entity)
-            .remove::<RigidBody>()
-            .remove::<Collider>()
-            .remove::<LinearVelocity>()
-            .remove::<AngularVelocity>()
-            .insert((
-                HeldClock,
-                ChildOf(*camera),
-                Transform::from_translation(Vec3::new(0.3, -0.4, -0.8))
-                    .with_rotation(Quat::from_rotation_y(0.3))
-                    .with_scale(Vec3::splat(0.6)),
-            ));
-
-        info!("Reloj recogido!");
-        break;
+    // Si lleva algo en la mano, RMB lanza eso
+    if !mouse.just_pressed(MouseButton::Right) || !held.is_empty() || charges.charges == 0 {
+        return;
     }
+    charges.charges -= 1;
+
+    let forward = camera.forward();
+    commands.spawn((
+        Name::new("AlarmClock"),
+        AlarmClock { thrown_timer: 0.0, is_thrown: true },
+        SceneRoot(level_assets.alarm_clock_scene.clone()),
+        Transform::from_translation(camera.translation() + forward * 1.0),
+        RigidBody::Dynamic,
+        Collider::sphere(0.3),
+        LinearVelocity(forward * THROW_SPEED + Vec3::Y * 3.0),
+        AngularVelocity(Vec3::new(5.0, 3.0, 2.0)),
+    ));
+    info!("Reloj lanzado! Quedan {}", charges.charges);
 }
 
 // -----------------------------------------------
-// LANZAR
+// LANZAR — el agarre lo hace `grab`, aquí solo se arma el reloj
 // -----------------------------------------------
 
-fn player_throw_alarm_clock(
+fn arm_thrown_clock(
     mut commands: Commands,
-    mouse: Res<ButtonInput<MouseButton>>,
-    camera: Single<(&Transform, &GlobalTransform), With<Camera3d>>,
-    mut clocks: Query<(Entity, &mut AlarmClock), With<HeldClock>>,
+    mut thrown: MessageReader<PropThrown>,
+    mut clocks: Query<&mut AlarmClock>,
 ) {
-    if !mouse.just_pressed(MouseButton::Right) { return; }
-
-    let (_, cam_global) = *camera;
-
-    for (entity, mut clock) in clocks.iter_mut() {
-        let forward = cam_global.forward();
-        let throw_velocity = forward * THROW_SPEED + Vec3::Y * 3.0;
-        let world_pos = cam_global.translation() + forward * 1.0;
-
-        commands.entity(entity)
-            .remove::<HeldClock>()
-            .remove::<ChildOf>()
-            .insert((
-                Transform::from_translation(world_pos),
-                RigidBody::Dynamic,
-                Collider::sphere(0.3),
-                LinearVelocity(throw_velocity),
-                AngularVelocity(Vec3::new(5.0, 3.0, 2.0)),
-            ));
-
+    for ev in thrown.read() {
+        let Ok(mut clock) = clocks.get_mut(ev.entity) else { continue; };
         clock.is_thrown = true;
         clock.thrown_timer = 0.0;
+        commands.entity(ev.entity).remove::<Grabbable>();
         info!("Reloj lanzado!");
-        break;
     }
 }
 
@@ -205,7 +226,7 @@ fn alarm_clock_tick(
 // ESTRELLITAS
 // -----------------------------------------------
 
-fn spawn_stars(
+pub fn spawn_stars(
     commands: &mut Commands,
     meshes: &mut ResMut<Assets<Mesh>>,
     materials: &mut ResMut<Assets<StandardMaterial>>,
@@ -261,53 +282,153 @@ fn star_tick(
 // TIME FIELD
 // -----------------------------------------------
 
+impl TimeField {
+    /// Radio actual: crece rápido, se mantiene y se encoge al final
+    fn radius(&self) -> f32 {
+        let elapsed = TIME_FIELD_DURATION - self.timer;
+        let grow = (elapsed / TIME_FIELD_GROW).min(1.0);
+        let fade = (self.timer / TIME_FIELD_FADE).clamp(0.0, 1.0);
+        TIME_FIELD_RADIUS * grow * fade
+    }
+
+    /// Escala de tiempo a `distance` del centro: casi parado en el centro,
+    /// normal en el borde
+    fn scale_at(&self, distance: f32) -> f32 {
+        let radius = self.radius();
+        if radius <= 0.0 || distance >= radius {
+            return 1.0;
+        }
+        let t = distance / radius;
+        let eased = t * t * (3.0 - 2.0 * t);
+        TIME_FIELD_MIN_SCALE + (1.0 - TIME_FIELD_MIN_SCALE) * eased
+    }
+}
+
+impl TimeDilation {
+    fn new(scale: f32, base_gravity: f32) -> Self {
+        Self { scale, applied: 1.0, base_gravity }
+    }
+}
+
 fn time_field_tick(
     mut commands: Commands,
     time: Res<Time>,
     mut fields: Query<(Entity, &mut Transform, &mut TimeField)>,
-    enemies: Query<(Entity, &Transform), (With<Enemy>, Without<FrozenEnemy>, Without<TimeField>)>,
+    spatial_query: SpatialQuery,
+    collider_of: Query<&ColliderOf>,
+    bodies: Query<&RigidBody>,
+    // Enemigos y partículas se miran siempre (son pocos); lo ya frenado, para soltarlo
+    tracked: Query<Entity, (Or<(With<Enemy>, With<DarkParticle>, With<TimeDilation>)>, Without<Player>)>,
+    mut targets: Query<(&GlobalTransform, Option<&GravityScale>, Option<&mut TimeDilation>), Without<Player>>,
 ) {
     for (field_entity, mut field_transform, mut field) in fields.iter_mut() {
         field.timer -= time.delta_secs();
-
-        let elapsed = TIME_FIELD_DURATION - field.timer;
-        let scale = (elapsed / 0.3).min(1.0) * 5.0;
-        field_transform.scale = Vec3::splat(scale);
-
-        let field_pos = field_transform.translation;
-        for (enemy_entity, enemy_transform) in enemies.iter() {
-            if enemy_transform.translation.distance(field_pos) <= TIME_FIELD_RADIUS {
-                commands.entity(enemy_entity).insert(FrozenEnemy {
-                    remaining: FREEZE_DURATION,
-                });
-                info!("Enemigo paralizado!");
-            }
-        }
+        field_transform.scale = Vec3::splat(field.radius() / TIME_FIELD_RADIUS * 5.0);
 
         if field.timer <= 0.0 {
             commands.entity(field_entity).despawn();
             info!("Time field expirado");
         }
     }
+
+    // Del resto de cuerpos, solo los dinámicos que tocan algún campo
+    let mut candidates: Vec<Entity> = tracked.iter().collect();
+    for (_, transform, field) in fields.iter().filter(|(_, _, f)| f.timer > 0.0) {
+        let inside = spatial_query.shape_intersections(
+            &Collider::sphere(field.radius()),
+            transform.translation,
+            Quaternion::default(),
+            &SpatialQueryFilter::default(),
+        );
+        candidates.extend(
+            inside
+                .into_iter()
+                .map(|hit| collider_of.get(hit).map_or(hit, |c| c.body))
+                .filter(|body| bodies.get(*body).is_ok_and(|rb| rb.is_dynamic())),
+        );
+    }
+    candidates.sort_unstable();
+    candidates.dedup();
+
+    for entity in candidates {
+        let Ok((transform, gravity, dilation)) = targets.get_mut(entity) else { continue; };
+        let pos = transform.translation();
+        let scale = fields
+            .iter()
+            .filter(|(_, _, f)| f.timer > 0.0)
+            .map(|(_, t, f)| f.scale_at(t.translation.distance(pos)))
+            .fold(1.0_f32, f32::min);
+
+        match dilation {
+            Some(mut dilation) => dilation.scale = scale,
+            None if scale < 1.0 => {
+                commands
+                    .entity(entity)
+                    .insert(TimeDilation::new(scale, gravity.map_or(1.0, |g| g.0)));
+            }
+            None => {}
+        }
+    }
 }
 
 // -----------------------------------------------
-// ENEMIGOS CONGELADOS
+// DILATACIÓN — frena cuerpos y avisa al reanudar
 // -----------------------------------------------
 
-fn frozen_enemy_tick(
+fn apply_time_dilation(
     mut commands: Commands,
-    time: Res<Time>,
-    mut frozen: Query<(Entity, &mut FrozenEnemy, &mut LinearVelocity)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut dilated: Query<(
+        Entity,
+        &GlobalTransform,
+        &mut TimeDilation,
+        Option<&RigidBody>,
+        Option<&mut LinearVelocity>,
+        Option<&mut AngularVelocity>,
+        Has<Enemy>,
+    )>,
+) {
+    for (entity, transform, mut dilation, body, linear, angular, is_enemy) in dilated.iter_mut() {
+        if body.is_some_and(|rb| rb.is_dynamic()) {
+            // Reescala la velocidad al cambiar la escala: entrar frena,
+            // salir devuelve la velocidad que llevaba
+            let ratio = dilation.scale / dilation.applied.max(TIME_FIELD_MIN_SCALE);
+            if let Some(mut linear) = linear {
+                linear.0 *= ratio;
+            }
+            if let Some(mut angular) = angular {
+                angular.0 *= ratio;
+            }
+            // La gravedad escala con el cuadrado del tiempo
+            commands
+                .entity(entity)
+                .insert(GravityScale(dilation.base_gravity * dilation.scale * dilation.scale));
+        }
+        dilation.applied = dilation.scale;
+
+        if dilation.scale < 1.0 {
+            continue;
+        }
+
+        commands.entity(entity).remove::<TimeDilation>();
+        if is_enemy {
+            spawn_stars(&mut commands, &mut meshes, &mut materials, transform.translation() + Vec3::Y * 2.5);
+            info!("Enemigo reanudado");
+        }
+    }
+}
+
+fn dilate_enemy_animations(
+    enemies: Query<Option<&TimeDilation>, With<Enemy>>,
+    mut anim_players: Query<(&EnemyAnimationPlayer, &mut AnimationPlayer, Option<&EnemyAnimations>)>,
 ) {
-    for (entity, mut frozen, mut velocity) in frozen.iter_mut() {
-        velocity.x = 0.0;
-        velocity.z = 0.0;
-
-        frozen.remaining -= time.delta_secs();
-        if frozen.remaining <= 0.0 {
-            commands.entity(entity).remove::<FrozenEnemy>();
-            info!("Enemigo descongelado");
+    for (link, mut player, animations) in anim_players.iter_mut() {
+        let Ok(dilation) = enemies.get(link.enemy) else { continue; };
+        let scale = dilation.map_or(1.0, |d| d.scale);
+        let base = animations.map_or(1.0, |a| a.speed);
+        for (_, animation) in player.playing_animations_mut() {
+            animation.set_speed(base * scale);
         }
     }
 }
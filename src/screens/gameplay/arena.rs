@@ -1,17 +1,328 @@
+//! Arenas
+//!
+//! Una arena se monta en Blender con componentes de Skein:
+//!
+//! - `Arena` en un volumen con collider. Si no lleva ya un `Trigger`, se le
+//!   pone uno de solo jugador (ver `trigger`). Su `zone` es la de las
+//!   oleadas del director.
+//! - `ArenaBarrier` en las paredes que cierran entradas y salidas. Están
+//!   bajadas (ni se ven ni chocan) hasta que la arena se sella. El collider
+//!   tiene que estar en el propio objeto o en sus hijos.
+//! - `ArenaFloor` en las piezas del suelo que se pueden hundir.
+//!
+//! Al entrar el jugador se levantan las barreras, hay una cuenta atrás
+//! (`countdown`) y se piden las oleadas de la zona. Si el director sigue con
+//! otra, la arena espera sellada a que le toque (`ArenaState::Queued`). Si la arena tiene
+//! `time_limit` y se acaba el tiempo sin despejarla, el suelo se desmorona
+//! de fuera hacia dentro: cada pieza tiembla un momento y pasa a dinámica
+//! (`ArenaCollapsedEvent`). Al completar la zona se bajan las barreras
+//! (`ArenaClearedEvent`).
+//!
+//! En supervivencia las arenas no hacen nada: las barreras se quedan bajadas
+//! y sus oleadas no se piden, porque el director está en la zona sin fin.
+
+use avian3d::prelude::*;
 use bevy::prelude::*;
+use rand::RngExt;
+
+use crate::PausableSystems;
+use crate::screens::Screen;
+use crate::screens::gameplay::{
+    events::{ArenaClearedEvent, ArenaCollapsedEvent, ZoneCompletedEvent},
+    spawn_enemy_waves::WaveDirector,
+    survival::survival_mode,
+    trigger::{Trigger, TriggerEvent, TriggerFilter, TriggerKind},
+};
+
+pub struct ArenaPlugin;
+
+/// Cuenta atrás si el `Arena` de Blender no dice otra
+const DEFAULT_COUNTDOWN: f32 = 3.0;
+/// Segundos entre pieza y pieza del suelo al hundirse
+const COLLAPSE_INTERVAL: f32 = 0.6;
+/// Lo que tiembla una pieza antes de caer
+const CRUMBLE_TIME: f32 = 0.8;
+const CRUMBLE_SHAKE: f32 = 0.06;
 
-use pub struct ArenaPlugin {}
+impl Plugin for ArenaPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Arena>();
+        app.register_type::<ArenaBarrier>();
+        app.register_type::<ArenaFloor>();
+        app.add_message::<ArenaCollapsedEvent>();
+        app.add_message::<ArenaClearedEvent>();
+        app.add_systems(
+            Update,
+            (
+                lower_new_barriers,
+                (
+                    setup_arenas,
+                    enter_arena,
+                    arena_timers,
+                    clear_arenas,
+                    collapse_floor,
+                    crumble_tick,
+                )
+                    .chain()
+                    .run_if(not(survival_mode)),
+            )
+                .chain()
+                .in_set(PausableSystems)
+                .run_if(in_state(Screen::Gameplay)),
+        );
+    }
+}
+
+// -----------------------------------------------
+// COMPONENTES DE BLENDER
+// -----------------------------------------------
 
-impl Plugin for ArenaPlugin {}
+#[derive(Component, Reflect, Default)]
+#[reflect(Component, Default)]
+pub struct Arena {
+    /// Zona de oleadas que arranca (la `zone` de los `EnemySpawn`)
+    pub zone: String,
+    /// Segundos antes de la primera oleada (0 = la de por defecto)
+    pub countdown: f32,
+    /// Segundos para despejarla antes de que se hunda el suelo (0 = sin límite)
+    pub time_limit: f32,
+}
 
-fn countdown() {
+#[derive(Component, Reflect, Default)]
+#[reflect(Component, Default)]
+pub struct ArenaBarrier {
+    pub zone: String,
+}
 
+#[derive(Component, Reflect, Default)]
+#[reflect(Component, Default)]
+pub struct ArenaFloor {
+    pub zone: String,
 }
 
-fn arena_collapse() {
+// -----------------------------------------------
+// ESTADO
+// -----------------------------------------------
 
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum ArenaState {
+    /// Nadie ha entrado todavía
+    Waiting,
+    Countdown { remaining: f32 },
+    /// Zona pedida, pero el director aún está con otra
+    Queued,
+    Fighting { elapsed: f32 },
+    /// Se acabó el tiempo: se sigue peleando mientras se hunde el suelo
+    Collapsing { next_piece: f32 },
+    Cleared { elapsed: f32 },
 }
 
-fn timer_logic() {
+/// Cómo va una arena (en la misma entidad que `Arena`)
+#[derive(Component, Debug)]
+pub struct ArenaRun {
+    pub state: ArenaState,
+    /// Piezas del suelo que quedan por caer, la siguiente al final
+    collapse_queue: Vec<Entity>,
+}
+
+/// Pieza del suelo temblando antes de caer
+#[derive(Component)]
+struct Crumbling {
+    timer: Timer,
+    origin: Vec3,
+}
+
+fn setup_arenas(mut commands: Commands, arenas: Query<(Entity, &Arena, Has<Trigger>), Added<Arena>>) {
+    for (entity, arena, has_trigger) in &arenas {
+        info!("Arena detectada: '{}'", arena.zone);
+        commands
+            .entity(entity)
+            .insert(ArenaRun { state: ArenaState::Waiting, collapse_queue: Vec::new() });
+        if !has_trigger {
+            commands.entity(entity).insert(Trigger {
+                action: "arena".to_string(),
+                filter: TriggerFilter::Player,
+                once: false,
+                stay_interval: 0.0,
+            });
+        }
+    }
+}
+
+fn lower_new_barriers(
+    mut commands: Commands,
+    barriers: Query<Entity, Added<ArenaBarrier>>,
+    children: Query<&Children>,
+) {
+    for entity in &barriers {
+        set_barrier(&mut commands, entity, &children, false);
+    }
+}
+
+/// Levanta o baja una barrera con todos sus hijos
+fn set_barrier(commands: &mut Commands, barrier: Entity, children: &Query<&Children>, raised: bool) {
+    for entity in std::iter::once(barrier).chain(children.iter_descendants(barrier)) {
+        if raised {
+            commands.entity(entity).remove::<ColliderDisabled>().insert(Visibility::Inherited);
+        } else {
+            commands.entity(entity).insert((ColliderDisabled, Visibility::Hidden));
+        }
+    }
+}
+
+fn set_zone_barriers(
+    commands: &mut Commands,
+    zone: &str,
+    barriers: &Query<(Entity, &ArenaBarrier)>,
+    children: &Query<&Children>,
+    raised: bool,
+) {
+    for (entity, _) in barriers.iter().filter(|(_, barrier)| barrier.zone == zone) {
+        set_barrier(commands, entity, children, raised);
+    }
+}
+
+/// El jugador entra en una arena que espera: se sella
+fn enter_arena(
+    mut commands: Commands,
+    mut triggers: MessageReader<TriggerEvent>,
+    mut arenas: Query<(&Arena, &mut ArenaRun)>,
+    barriers: Query<(Entity, &ArenaBarrier)>,
+    children: Query<&Children>,
+    director: Res<WaveDirector>,
+) {
+    for ev in triggers.read().filter(|ev| ev.kind == TriggerKind::Enter) {
+        let Ok((arena, mut run)) = arenas.get_mut(ev.trigger) else { continue; };
+        if run.state != ArenaState::Waiting {
+            continue;
+        }
+
+        // La zona ya se jugó (p. ej. era `autostart`): no hay nada que sellar
+        if director.is_complete(&arena.zone) {
+            run.state = ArenaState::Cleared { elapsed: 0.0 };
+            continue;
+        }
+
+        info!("🏟️ Arena '{}' sellada", arena.zone);
+        set_zone_barriers(&mut commands, &arena.zone, &barriers, &children, true);
+        let countdown = if arena.countdown > 0.0 { arena.countdown } else { DEFAULT_COUNTDOWN };
+        run.state = ArenaState::Countdown { remaining: countdown };
+    }
+}
+
+fn arena_timers(
+    time: Res<Time>,
+    mut arenas: Query<(&Arena, &GlobalTransform, &mut ArenaRun)>,
+    floors: Query<(Entity, &GlobalTransform, &ArenaFloor)>,
+    mut director: ResMut<WaveDirector>,
+    mut collapsed_writer: MessageWriter<ArenaCollapsedEvent>,
+) {
+    let dt = time.delta_secs();
+    for (arena, arena_transform, mut run) in arenas.iter_mut() {
+        match &mut run.state {
+            ArenaState::Countdown { remaining } => {
+                *remaining -= dt;
+                if *remaining <= 0.0 {
+                    director.start_zone(arena.zone.clone());
+                    run.state = ArenaState::Queued;
+                }
+            }
+            ArenaState::Queued => {
+                // El tiempo límite no corre hasta que salen sus oleadas
+                if director.current_zone() == Some(arena.zone.as_str()) {
+                    run.state = ArenaState::Fighting { elapsed: 0.0 };
+                }
+            }
+            ArenaState::Fighting { elapsed } => {
+                *elapsed += dt;
+                if arena.time_limit <= 0.0 || *elapsed < arena.time_limit {
+                    continue;
+                }
+                // De fuera hacia dentro: lo más lejano del centro, al final de la cola
+                let center = arena_transform.translation();
+                let mut pieces: Vec<(Entity, f32)> = floors
+                    .iter()
+                    .filter(|(_, _, floor)| floor.zone == arena.zone)
+                    .map(|(entity, transform, _)| (entity, transform.translation().distance_squared(center)))
+                    .collect();
+                pieces.sort_by(|a, b| a.1.total_cmp(&b.1));
+                if pieces.is_empty() {
+                    warn!("⚠️ La arena '{}' no tiene ArenaFloor que hundir", arena.zone);
+                }
+                warn!("⏱️ Se acabó el tiempo en la arena '{}': el suelo se hunde", arena.zone);
+                collapsed_writer.write(ArenaCollapsedEvent { zone: arena.zone.clone() });
+                run.collapse_queue = pieces.into_iter().map(|(entity, _)| entity).collect();
+                run.state = ArenaState::Collapsing { next_piece: 0.0 };
+            }
+            ArenaState::Cleared { elapsed } => *elapsed += dt,
+            ArenaState::Waiting | ArenaState::Collapsing { .. } => {}
+        }
+    }
+}
+
+/// La zona de la arena se completó: se abren las salidas
+fn clear_arenas(
+    mut commands: Commands,
+    mut completed: MessageReader<ZoneCompletedEvent>,
+    mut arenas: Query<(&Arena, &mut ArenaRun)>,
+    barriers: Query<(Entity, &ArenaBarrier)>,
+    children: Query<&Children>,
+    mut cleared_writer: MessageWriter<ArenaClearedEvent>,
+) {
+    for event in completed.read() {
+        for (arena, mut run) in arenas.iter_mut().filter(|(arena, _)| arena.zone == event.zone) {
+            if matches!(run.state, ArenaState::Waiting | ArenaState::Cleared { .. }) {
+                continue;
+            }
+            info!("🏟️ Arena '{}' despejada", arena.zone);
+            set_zone_barriers(&mut commands, &arena.zone, &barriers, &children, false);
+            run.state = ArenaState::Cleared { elapsed: 0.0 };
+            run.collapse_queue.clear();
+            cleared_writer.write(ArenaClearedEvent { zone: arena.zone.clone() });
+        }
+    }
+}
+
+fn collapse_floor(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut arenas: Query<&mut ArenaRun>,
+    transforms: Query<&Transform>,
+) {
+    for mut run in arenas.iter_mut() {
+        let ArenaState::Collapsing { next_piece } = &mut run.state else { continue; };
+        *next_piece -= time.delta_secs();
+        if *next_piece > 0.0 {
+            continue;
+        }
+        *next_piece = COLLAPSE_INTERVAL;
+        let Some(piece) = run.collapse_queue.pop() else { continue; };
+        let Ok(transform) = transforms.get(piece) else { continue; };
+        commands.entity(piece).insert(Crumbling {
+            timer: Timer::from_seconds(CRUMBLE_TIME, TimerMode::Once),
+            origin: transform.translation,
+        });
+    }
+}
 
+fn crumble_tick(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut pieces: Query<(Entity, &mut Crumbling, &mut Transform)>,
+) {
+    let mut rng = rand::rng();
+    for (entity, mut crumbling, mut transform) in pieces.iter_mut() {
+        crumbling.timer.tick(time.delta());
+        if crumbling.timer.is_finished() {
+            transform.translation = crumbling.origin;
+            commands.entity(entity).remove::<Crumbling>().insert(RigidBody::Dynamic);
+            continue;
+        }
+        let shake = Vec3::new(
+            rng.random_range(-1.0..1.0),
+            rng.random_range(-0.5..0.5),
+            rng.random_range(-1.0..1.0),
+        );
+        transform.translation = crumbling.origin + shake * CRUMBLE_SHAKE * crumbling.timer.fraction();
+    }
 }
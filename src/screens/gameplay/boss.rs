@@ -0,0 +1,279 @@
+//! Jefes
+//!
+//! Un jefe es un enemigo normal (su tipo en `assets/enemies/`) con sección
+//! `boss`: un título y una lista de fases. Cada fase empieza cuando la vida
+//! baja de su umbral y puede:
+//!
+//! - cambiar el ataque y los tiempos del `EnemyBehaviour`,
+//! - cambiar la arena (quitar o tirar objetos de Blender por nombre),
+//! - sacar esbirros cada cierto tiempo.
+//!
+//! Al morir manda `BossDefeatedEvent`, que es lo que mira `objective`. La
+//! barra de vida del jefe va en el HUD.
+
+use avian3d::prelude::*;
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::PausableSystems;
+use crate::screens::Screen;
+use crate::screens::gameplay::{
+    enemy::Enemy,
+    enemy_ai::{EnemyAttack, EnemyBehaviour},
+    enemy_archetype::{EnemyArchetype, EnemyArchetypeHandle},
+    events::EnemyKilledEvent,
+    spawn_telegraph::{SpawnTelegraph, TelegraphedSpawnCmd},
+};
+
+pub struct BossPlugin;
+
+/// Distancia al jefe a la que aparecen sus esbirros
+const MINION_RING_RADIUS: f32 = 6.0;
+
+impl Plugin for BossPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<BossPhaseChanged>();
+        app.add_message::<BossDefeatedEvent>();
+        app.add_systems(
+            Update,
+            (boss_phase_transitions, apply_arena_changes, boss_minions, boss_defeated)
+                .chain()
+                .in_set(PausableSystems)
+                .run_if(in_state(Screen::Gameplay)),
+        );
+    }
+}
+
+// -----------------------------------------------
+// CONFIGURACIÓN (sección `boss` del `.enemy.ron`)
+// -----------------------------------------------
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct BossSettings {
+    /// Nombre que sale en la barra del HUD
+    pub title: String,
+    /// En orden; la primera debería tener `health_below: 1.0`
+    pub phases: Vec<BossPhase>,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct BossPhase {
+    /// Empieza cuando la vida (0..1) baja de esto
+    pub health_below: f32,
+    #[serde(default)]
+    pub attack: Option<EnemyAttack>,
+    #[serde(default)]
+    pub chase_speed: Option<f32>,
+    #[serde(default)]
+    pub attack_cooldown: Option<f32>,
+    #[serde(default)]
+    pub windup_time: Option<f32>,
+    #[serde(default)]
+    pub arena: Vec<ArenaChange>,
+    #[serde(default)]
+    pub minions: Vec<MinionSpawn>,
+}
+
+/// Cambios en la arena al entrar en una fase. El texto es el nombre del
+/// objeto de Blender, o de varios con el sufijo de duplicado (`BossPillar`,
+/// `BossPillar.001`...). Solo cuenta la geometría con collider o cuerpo.
+#[derive(Deserialize, Clone, Debug)]
+pub enum ArenaChange {
+    /// Desaparece
+    Remove(String),
+    /// Se vuelve dinámico y cae
+    Drop(String),
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct MinionSpawn {
+    pub archetype: String,
+    pub count: usize,
+    /// Segundos entre tandas
+    pub interval: f32,
+    /// No saca más si ya hay tantos vivos de este tipo
+    pub max_alive: usize,
+}
+
+// -----------------------------------------------
+// COMPONENTES / MENSAJES
+// -----------------------------------------------
+
+#[derive(Component)]
+pub struct Boss {
+    pub settings: BossSettings,
+    pub phase: usize,
+    minion_timers: Vec<Timer>,
+}
+
+impl Boss {
+    pub fn new(settings: BossSettings) -> Self {
+        Self {
+            settings,
+            phase: usize::MAX,
+            minion_timers: Vec::new(),
+        }
+    }
+
+    pub fn title(&self) -> &str {
+        &self.settings.title
+    }
+
+    pub fn phase_count(&self) -> usize {
+        self.settings.phases.len()
+    }
+}
+
+#[derive(Message, Clone, Copy, Debug)]
+pub struct BossPhaseChanged {
+    pub boss: Entity,
+    pub phase: usize,
+}
+
+#[derive(Message, Clone, Copy, Debug)]
+pub struct BossDefeatedEvent {
+    pub boss: Entity,
+}
+
+// -----------------------------------------------
+// SISTEMAS
+// -----------------------------------------------
+
+fn boss_phase_transitions(
+    mut bosses: Query<(Entity, &Enemy, &mut Boss, &mut EnemyBehaviour)>,
+    mut phase_writer: MessageWriter<BossPhaseChanged>,
+) {
+    for (entity, enemy, mut boss, mut behaviour) in bosses.iter_mut() {
+        let fraction = enemy.health as f32 / enemy.max_health.max(1) as f32;
+        // La última fase cuyo umbral ya se ha pasado
+        let Some(phase) = boss.settings.phases.iter().rposition(|p| fraction <= p.health_below) else { continue; };
+        if boss.phase != usize::MAX && phase <= boss.phase {
+            continue;
+        }
+
+        let settings = boss.settings.phases[phase].clone();
+        if let Some(attack) = settings.attack {
+            behaviour.attack = attack;
+        }
+        if let Some(speed) = settings.chase_speed {
+            behaviour.chase_speed = speed;
+        }
+        if let Some(cooldown) = settings.attack_cooldown {
+            behaviour.attack_cooldown = cooldown;
+        }
+        if let Some(windup) = settings.windup_time {
+            behaviour.windup_time = windup;
+        }
+        boss.minion_timers = settings
+            .minions
+            .iter()
+            .map(|m| Timer::from_seconds(m.interval, TimerMode::Repeating))
+            .collect();
+        boss.phase = phase;
+
+        info!("Jefe '{}' entra en fase {}/{}", boss.title(), phase + 1, boss.phase_count());
+        phase_writer.write(BossPhaseChanged { boss: entity, phase });
+    }
+}
+
+fn apply_arena_changes(
+    mut commands: Commands,
+    mut changes: MessageReader<BossPhaseChanged>,
+    bosses: Query<&Boss>,
+    objects: Query<(Entity, &Name), (Or<(With<Collider>, With<RigidBody>)>, Without<Enemy>)>,
+) {
+    for change in changes.read() {
+        let Ok(boss) = bosses.get(change.boss) else { continue; };
+        for arena_change in &boss.settings.phases[change.phase].arena {
+            let (pattern, drop) = match arena_change {
+                ArenaChange::Remove(pattern) => (pattern, false),
+                ArenaChange::Drop(pattern) => (pattern, true),
+            };
+            let mut found = 0;
+            for (entity, name) in objects.iter().filter(|(_, n)| blender_name_matches(n.as_str(), pattern)) {
+                if drop {
+                    commands.entity(entity).insert(RigidBody::Dynamic);
+                } else {
+                    commands.entity(entity).despawn();
+                }
+                found += 1;
+                debug!("Arena: {:?} '{}'", arena_change, name);
+            }
+            if found == 0 {
+                warn!("Cambio de arena sin objetos llamados '{}'", pattern);
+            }
+        }
+    }
+}
+
+/// `name` es `pattern` o una copia suya de Blender (`pattern.001`)
+fn blender_name_matches(name: &str, pattern: &str) -> bool {
+    match name.strip_prefix(pattern) {
+        Some("") => true,
+        Some(suffix) => suffix
+            .strip_prefix('.')
+            .is_some_and(|n| !n.is_empty() && n.chars().all(|c| c.is_ascii_digit())),
+        None => false,
+    }
+}
+
+fn boss_minions(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut bosses: Query<(&Transform, &mut Boss), With<Enemy>>,
+    alive: Query<&EnemyArchetypeHandle, Or<(With<Enemy>, With<SpawnTelegraph>)>>,
+    archetypes: Res<Assets<EnemyArchetype>>,
+) {
+    for (transform, mut boss) in bosses.iter_mut() {
+        if boss.phase == usize::MAX {
+            continue;
+        }
+        let phase = boss.phase;
+        let mut to_spawn = Vec::new();
+        for (i, timer) in boss.minion_timers.iter_mut().enumerate() {
+            timer.tick(time.delta());
+            if timer.just_finished() {
+                to_spawn.push(i);
+            }
+        }
+
+        for i in to_spawn {
+            let minion = &boss.settings.phases[phase].minions[i];
+            let alive_count = alive
+                .iter()
+                .filter(|handle| archetypes.get(&handle.0).is_some_and(|a| a.def.name == minion.archetype))
+                .count();
+            let count = minion.count.min(minion.max_alive.saturating_sub(alive_count));
+            for n in 0..count {
+                let angle = std::f32::consts::TAU * n as f32 / count as f32;
+                let offset = Quat::from_rotation_y(angle) * Vec3::X * MINION_RING_RADIUS;
+                commands.queue(TelegraphedSpawnCmd {
+                    archetype: minion.archetype.clone(),
+                    position: transform.translation + offset,
+                    source: format!("esbirro {} de {}", n + 1, boss.title()),
+                    wave: None,
+                });
+            }
+        }
+    }
+}
+
+/// Los jefes conocidos se guardan aparte: si cae al vacío, cuando llega el
+/// mensaje la entidad ya no existe
+fn boss_defeated(
+    mut killed: MessageReader<EnemyKilledEvent>,
+    bosses: Query<(Entity, &Boss)>,
+    mut known: Local<HashMap<Entity, String>>,
+    mut defeated_writer: MessageWriter<BossDefeatedEvent>,
+) {
+    for (entity, boss) in &bosses {
+        known.entry(entity).or_insert_with(|| boss.title().to_string());
+    }
+    for event in killed.read() {
+        if let Some(title) = known.remove(&event.enemy) {
+            info!("Jefe '{}' derrotado", title);
+            defeated_writer.write(BossDefeatedEvent { boss: event.enemy });
+        }
+    }
+}
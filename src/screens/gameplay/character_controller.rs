@@ -6,7 +6,14 @@ use bevy::{
 };
 use bevy_seedling::{prelude::LowPassNode, sample_effects};
 
-use super::enemy::{Enemy, Knockback};
+use super::charge_attack::Charging;
+use super::enemy::{Enemy, EnemySounds, Knockback};
+use super::enemy_ai::EnemyBehaviour;
+use super::grab::Grabbable;
+use super::hitstop::MeleeImpact;
+use super::ledge::LastHitBy;
+use super::poise::{Poise, PoiseBroken};
+use super::powerups::{DamageBoost, gain_more_damage};
 use crate::{
     PausableSystems,
     audio::sound_effect,
@@ -59,9 +66,27 @@ pub enum MovementAction {
 
 #[derive(Message)]
 pub enum AttackAction {
+    /// Patada — siempre disponible, aunque no tengas arma
     Punch(Dir3),
+    /// Golpe con el arma equipada
+    Weapon { forward: Dir3, damage: u32, poise_damage: f32, range: f32 },
 }
 
+impl AttackAction {
+    /// Hacia dónde y hasta dónde llega el golpe
+    pub fn reach(&self) -> (Dir3, f32) {
+        match self {
+            AttackAction::Punch(forward) => (*forward, KICK_RANGE),
+            AttackAction::Weapon { forward, range, .. } => (*forward, *range),
+        }
+    }
+}
+
+/// Alcance, daño y poise de la patada
+const KICK_RANGE: f32 = 4.5;
+const KICK_DAMAGE: u32 = 1;
+const KICK_POISE: f32 = 4.0;
+
 #[derive(Component)]
 pub struct CameraRotation(pub f32);
 
@@ -333,13 +358,25 @@ fn update_camera_rotation(
 
 fn attack(
     mut attack_reader: MessageReader<AttackAction>,
+    mut impact_writer: MessageWriter<MeleeImpact>,
+    mut poise_writer: MessageWriter<PoiseBroken>,
     mut commands: Commands,
     player_transform: Single<&Transform, With<Player>>,
+    player_entity: Single<Entity, With<Player>>,
+    damage_boost: Query<&DamageBoost, With<Player>>,
     mut punchables: Query<
         (&GlobalTransform, Forces),
         (With<Collider>, Without<Player>, Without<Enemy>, Without<FlowerCapsule>),
     >,
-    mut enemies: Query<(Entity, &GlobalTransform, &mut Enemy)>,
+    mut enemies: Query<(
+        Entity,
+        &GlobalTransform,
+        &mut Enemy,
+        &EnemySounds,
+        Option<&mut Poise>,
+        Option<&EnemyBehaviour>,
+        Has<Charging>,
+    )>,
     mut capsules: Query<(Entity, &GlobalTransform, &mut FlowerCapsule)>,
     shards: Query<(Entity, &ShardOwner)>,
     mut tracker: ResMut<CapsuleTracker>,
@@ -350,8 +387,8 @@ fn attack(
         target_transform: &GlobalTransform,
         player_transform: &Transform,
         punch_forward: &Dir3,
+        range: f32,
     ) -> Option<Vec3> {
-        const PUNCH_RANGE: f32 = 4.5;
         const PUNCH_FORCE: f32 = 7.0;
         const MIN_DOT_PRODUCT: f32 = 0.75;
 
@@ -359,7 +396,7 @@ fn attack(
         let to_object_from_player = target_pos - player_transform.translation;
         let distance = to_object_from_player.length();
 
-        if distance > PUNCH_RANGE {
+        if distance > range {
             debug!("too far ({distance})");
             return None;
         }
@@ -375,69 +412,95 @@ fn attack(
     }
 
     for event in attack_reader.read() {
-        match event {
-            AttackAction::Punch(punch_forward) => {
-                // Objetos físicos normales
-                for (transform, mut forces) in punchables.iter_mut() {
-                    if let Some(impulse) = punch_impulse(transform, *player_transform, punch_forward) {
-                        forces.apply_linear_impulse(impulse);
-                    }
-                }
+        let (punch_forward, damage, poise_damage, range) = match event {
+            AttackAction::Punch(forward) => (forward, KICK_DAMAGE, KICK_POISE, KICK_RANGE),
+            AttackAction::Weapon { forward, damage, poise_damage, range } => {
+                (forward, *damage, *poise_damage, *range)
+            }
+        };
+        let damage = gain_more_damage(damage, damage_boost.single().ok());
 
-                // Enemigos — sonido diferente según si es primer hit o hit final
-                let mut hit_something = false;
-                for (entity, transform, mut enemy) in enemies.iter_mut() {
-                    if let Some(impulse) = punch_impulse(transform, *player_transform, punch_forward) {
-                        if enemy.health > 0 {
-                            enemy.health -= 1;
-                            hit_something = true;
-                            if enemy.health > 0 {
-                                // Primer hit — sword impact + knockback
-                                commands.entity(*level).with_child(sound_effect(
-                                    level_assets.hit_enemy_first.clone(),
-                                    (),
-                                ));
-                                commands.entity(entity).insert(Knockback {
-                                    velocity: impulse,
-                                    remaining_time: 0.3,
-                                });
-                            } else {
-                                // Hit final — sonido de muerte + despawn
-                                commands.entity(*level).with_child(sound_effect(
-                                    level_assets.hit_enemy_final.clone(),
-                                    (),
-                                ));
-                                commands.entity(entity).despawn();
-                            }
-                        }
-                    }
-                }
+        // Objetos físicos normales
+        for (transform, mut forces) in punchables.iter_mut() {
+            if let Some(impulse) = punch_impulse(transform, *player_transform, punch_forward, range) {
+                forces.apply_linear_impulse(impulse);
+            }
+        }
 
-                // Cápsulas — sonido de cristal al golpear
-                for (entity, transform, mut capsule) in capsules.iter_mut() {
-                    if punch_impulse(transform, *player_transform, punch_forward).is_some() {
-                        hit_something = true;
-                        damage_capsule(
-                            &mut commands,
-                            entity,
-                            &mut capsule,
-                            &mut tracker,
-                            &shards,
-                            &level_assets,
-                            *level,
-                        );
+        // Enemigos — sonido diferente según si es primer hit o hit final
+        let mut hit_something = false;
+        for (entity, transform, mut enemy, sounds, poise, behaviour, charging) in enemies.iter_mut() {
+            if let Some(impulse) = punch_impulse(transform, *player_transform, punch_forward, range) {
+                if enemy.health > 0 {
+                    let dealt = damage.min(enemy.health);
+                    enemy.health -= dealt;
+                    hit_something = true;
+                    // Con hyper-armor el golpe hace daño pero no gasta poise
+                    let hyper_armor = charging && behaviour.is_some_and(|b| b.attack.hyper_armor());
+                    let staggered = !hyper_armor
+                        && match poise {
+                            Some(mut poise) => poise.hit(poise_damage),
+                            None => true,
+                        };
+                    if staggered && enemy.health > 0 {
+                        poise_writer.write(PoiseBroken {
+                            enemy: entity,
+                            position: transform.translation(),
+                        });
+                    }
+                    commands.entity(entity).insert(LastHitBy::new(*player_entity));
+                    impact_writer.write(MeleeImpact {
+                        enemy: entity,
+                        damage: dealt,
+                        lethal: enemy.health == 0,
+                        staggered,
+                    });
+                    if enemy.health > 0 {
+                        // Primer hit — sword impact, y knockback si rompió la poise
+                        commands.entity(*level).with_child(sound_effect(
+                            sounds.hit.clone(),
+                            (),
+                        ));
+                        if staggered {
+                            commands.entity(entity).insert(Knockback {
+                                velocity: impulse,
+                                remaining_time: 0.3,
+                            });
+                        }
+                    } else {
+                        // Hit final — sonido de muerte; `enemy_health_system` lo mata
+                        commands.entity(*level).with_child(sound_effect(
+                            sounds.death.clone(),
+                            (),
+                        ));
                     }
                 }
+            }
+        }
 
-                // Whoosh siempre al atacar (swing de espada)
-                commands.entity(*level).with_child(sound_effect(
-                    level_assets.whoosh1.clone(),
-                    (),
-                ));
-
-                let _ = hit_something; // suprime warning si no se usa
+        // Cápsulas — sonido de cristal al golpear
+        for (entity, transform, mut capsule) in capsules.iter_mut() {
+            if punch_impulse(transform, *player_transform, punch_forward, range).is_some() {
+                hit_something = true;
+                damage_capsule(
+                    &mut commands,
+                    entity,
+                    &mut capsule,
+                    &mut tracker,
+                    &shards,
+                    &level_assets,
+                    *level,
+                );
             }
         }
+
+        // Whoosh siempre al atacar (swing de espada)
+        commands.entity(*level).with_child(sound_effect(
+            level_assets.whoosh1.clone(),
+            (),
+        ));
+
+        let _ = hit_something; // suprime warning si no se usa
     }
 }
 
@@ -456,6 +519,7 @@ fn spawn_something_punchable(
             RigidBody::Dynamic,
             Collider::cuboid(1.0, 1.0, 1.0),
             Mass(5.0),
+            Grabbable,
         ))
         .id();
     commands.entity(*level).add_child(cube);
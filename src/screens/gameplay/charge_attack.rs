@@ -0,0 +1,179 @@
+//! Embestida del hammerhead
+//!
+//! Al entrar en `Attack` con un `EnemyAttack::Charge` se fija la dirección
+//! hacia el jugador y el enemigo acelera en línea recta sin corregir. Solo
+//! hace daño si su collider choca de verdad con el del jugador. Si se come
+//! una pared queda aturdido, y como no frena en los bordes puede caerse de
+//! la nube. El cabezazo se lanza justo a tiempo para que el golpe del clip
+//! coincida con el impacto.
+
+use std::time::Duration;
+
+use avian3d::prelude::*;
+use bevy::prelude::*;
+
+use crate::screens::gameplay::{
+    alarm_clock::{TimeDilation, spawn_stars},
+    enemy::{Enemy, EnemyAnimationLink, EnemyAnimations},
+    enemy_ai::{EnemyAttack, EnemyBehaviour},
+    player::Player,
+};
+
+/// Altura del pecho del enemigo, desde donde se busca la pared
+const CHEST_HEIGHT: f32 = 1.17;
+/// Radio del collider del enemigo
+const BODY_RADIUS: f32 = 0.45;
+/// Distancia entre centros a la que la cabeza toca al jugador
+const CONTACT_DISTANCE: f32 = 1.0;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum ChargeOutcome {
+    HitPlayer,
+    HitWall,
+}
+
+/// Embestida en curso
+#[derive(Component)]
+pub struct Charging {
+    pub dir: Vec3,
+    pub speed: f32,
+    bash_started: bool,
+    /// Lo rellena el choque; el cerebro decide el siguiente estado
+    pub outcome: Option<ChargeOutcome>,
+}
+
+impl Charging {
+    pub fn new(dir: Vec3) -> Self {
+        Self {
+            dir,
+            speed: 0.0,
+            bash_started: false,
+            outcome: None,
+        }
+    }
+}
+
+pub(super) fn charge_tick(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut enemies: Query<(
+        Entity,
+        &Transform,
+        &EnemyBehaviour,
+        &mut Charging,
+        &mut LinearVelocity,
+        &mut Rotation,
+        Option<&TimeDilation>,
+    )>,
+    links: Query<&EnemyAnimationLink>,
+    mut anim_players: Query<(&mut AnimationPlayer, &mut AnimationTransitions, &mut EnemyAnimations)>,
+    player: Single<&Transform, With<Player>>,
+    spatial_query: SpatialQuery,
+    colliders: Query<&ColliderOf>,
+    bodies: Query<&RigidBody>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    for (entity, transform, behaviour, mut charging, mut linear_velocity, mut rotation, dilation) in enemies.iter_mut() {
+        let EnemyAttack::Charge(settings) = &behaviour.attack else { continue; };
+
+        if charging.outcome.is_some() {
+            linear_velocity.x = 0.0;
+            linear_velocity.z = 0.0;
+            continue;
+        }
+
+        let time_scale = dilation.map_or(1.0, |d| d.scale);
+        let dt = time.delta_secs() * time_scale;
+        charging.speed = (charging.speed + settings.acceleration * dt).min(settings.max_speed);
+
+        let Ok(dir) = Dir3::new(charging.dir) else {
+            charging.outcome = Some(ChargeOutcome::HitWall);
+            continue;
+        };
+        *rotation = Quat::from_rotation_y((-dir.x).atan2(-dir.z)).into();
+
+        // Pared delante: solo cuenta la geometría estática
+        let look_ahead = BODY_RADIUS + charging.speed * dt + 0.1;
+        let wall = spatial_query.cast_ray_predicate(
+            transform.translation + Vec3::Y * CHEST_HEIGHT,
+            dir,
+            look_ahead,
+            true,
+            &SpatialQueryFilter::default(),
+            &|hit| {
+                let body = colliders.get(hit).map_or(hit, |c| c.body);
+                body != entity && bodies.get(body).is_ok_and(|rb| rb.is_static())
+            },
+        );
+        if wall.is_some() {
+            charging.outcome = Some(ChargeOutcome::HitWall);
+            linear_velocity.x = 0.0;
+            linear_velocity.z = 0.0;
+            spawn_stars(&mut commands, &mut meshes, &mut materials, transform.translation + Vec3::Y * 2.5);
+            info!("Hammerhead se estrelló contra una pared");
+            continue;
+        }
+
+        linear_velocity.x = dir.x * charging.speed * time_scale;
+        linear_velocity.z = dir.z * charging.speed * time_scale;
+
+        // Cabezazo: que el golpe del clip caiga justo en el impacto
+        if charging.bash_started {
+            continue;
+        }
+        let to_player = player.translation - transform.translation;
+        let ahead = to_player.dot(*dir);
+        let lateral = to_player.reject_from_normalized(*dir).with_y(0.0).length();
+        let time_to_impact = (ahead - CONTACT_DISTANCE) / (charging.speed * time_scale).max(0.1);
+        if ahead <= 0.0 || lateral > CONTACT_DISTANCE * 1.5 || time_to_impact > settings.bash_lead {
+            continue;
+        }
+
+        charging.bash_started = true;
+        let Some((mut anim_player, mut transitions, mut anims)) = links
+            .get(entity)
+            .ok()
+            .and_then(|link| anim_players.get_mut(link.player()).ok())
+        else {
+            continue;
+        };
+        let Some(&node) = anims.nodes.get(settings.bash_clip) else { continue; };
+        anims.speed = 1.0;
+        transitions
+            .play(&mut anim_player, node, Duration::from_millis(50))
+            .set_speed(1.0);
+    }
+}
+
+/// Daño solo si los colliders se tocan de verdad. Vale para cualquier ataque
+/// con daño por contacto (embestida o picado).
+pub(super) fn charge_impact(
+    mut collisions: MessageReader<CollisionStart>,
+    mut chargers: Query<(&EnemyBehaviour, &mut Charging), With<Enemy>>,
+    player: Single<(Entity, &mut Player, &mut LinearVelocity), Without<Enemy>>,
+) {
+    let (player_entity, mut player, mut player_velocity) = player.into_inner();
+
+    for ev in collisions.read() {
+        let (Some(body1), Some(body2)) = (ev.body1, ev.body2) else { continue; };
+        let enemy = if body2 == player_entity {
+            body1
+        } else if body1 == player_entity {
+            body2
+        } else {
+            continue;
+        };
+
+        let Ok((behaviour, mut charging)) = chargers.get_mut(enemy) else { continue; };
+        let Some((damage, knockback)) = behaviour.attack.contact_damage() else { continue; };
+        if charging.outcome.is_some() {
+            continue;
+        }
+
+        charging.outcome = Some(ChargeOutcome::HitPlayer);
+        player.health = (player.health - damage).max(0.0);
+        player_velocity.0 += charging.dir.with_y(0.0).normalize_or_zero() * knockback + Vec3::Y * 4.0;
+        info!("Embestida enemiga! Player health: {:.2}", player.health);
+    }
+}
@@ -1,7 +1,16 @@
 use avian3d::prelude::LinearVelocity;
 use bevy::{camera::visibility::NoFrustumCulling, prelude::*, scene::SceneInstanceReady};
 
-use crate::{menus::Menu, screens::gameplay::Player};
+use crate::{
+    menus::Menu,
+    screens::{
+        Screen,
+        gameplay::{
+            Player,
+            trigger::{TriggerEvent, TriggerKind},
+        },
+    },
+};
 
 pub struct CheckpointPlugin;
 
@@ -9,6 +18,7 @@ impl Plugin for CheckpointPlugin {
     fn build(&self, app: &mut App) {
         app.add_observer(move_player_to_checkpoint);
         app.add_systems(OnEnter(Menu::None), respawn_at_checkpoint);
+        app.add_systems(Update, activate_checkpoint.run_if(in_state(Screen::Gameplay)));
     }
 }
 
@@ -37,6 +47,24 @@ fn respawn_at_checkpoint(
     transform.translation = active_checkpoint.translation + Vec3::Y;
 }
 
+/// Un `Trigger` con `action: "checkpoint"` pasa a ser el checkpoint activo
+fn activate_checkpoint(
+    mut commands: Commands,
+    mut triggers: MessageReader<TriggerEvent>,
+    active: Query<Entity, With<ActiveCheckpoint>>,
+) {
+    for ev in triggers.read() {
+        if ev.kind != TriggerKind::Enter || ev.action != "checkpoint" || active.contains(ev.trigger) {
+            continue;
+        }
+        for previous in &active {
+            commands.entity(previous).remove::<ActiveCheckpoint>();
+        }
+        commands.entity(ev.trigger).insert((Checkpoint, ActiveCheckpoint));
+        info!("Checkpoint activado: {}", ev.trigger);
+    }
+}
+
 fn move_player_to_checkpoint(
     _: On<SceneInstanceReady>,
     mut commands: Commands,
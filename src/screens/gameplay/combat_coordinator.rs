@@ -0,0 +1,229 @@
+//! Coordinador de combate — tokens de ataque
+//!
+//! Solo los enemigos con `AttackToken` pueden empezar un windup. El
+//! coordinador reparte un número limitado de tokens (según la dificultad)
+//! a los que persiguen más cerca, y los recupera cuando terminan el ataque,
+//! se aturden, pierden al jugador o mueren. El resto rodea al jugador a
+//! media distancia, lo flanquea o espera su turno.
+//!
+//! `CombatCoordinator` está reflejado: se ve en el inspector de dev tools
+//! (y con el overlay de debug activo se dibuja quién tiene token).
+
+use bevy::prelude::*;
+
+use crate::PausableSystems;
+use crate::menus::Difficulty;
+use crate::screens::Screen;
+use crate::screens::gameplay::{
+    enemy::Enemy,
+    enemy_ai::{self, EnemyBrain, EnemyState, EnemyStateChanged},
+    perception::Perception,
+    player::Player,
+};
+
+pub struct CombatCoordinatorPlugin;
+
+/// Cuánto más allá del `attack_range` se puede pedir token
+const ENGAGE_MARGIN: f32 = 4.0;
+/// Si alguien tiene token y no ataca en este tiempo, lo pierde
+const TOKEN_TIMEOUT: f32 = 4.0;
+
+impl Plugin for CombatCoordinatorPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<CombatCoordinator>();
+        app.init_resource::<CombatCoordinator>();
+        app.add_systems(OnEnter(Screen::Gameplay), reset_coordinator);
+        app.add_systems(
+            Update,
+            (sync_difficulty, release_tokens, grant_tokens)
+                .chain()
+                .before(enemy_ai::update_enemy_brains)
+                .in_set(PausableSystems)
+                .run_if(in_state(Screen::Gameplay)),
+        );
+        #[cfg(feature = "dev")]
+        app.add_systems(
+            Update,
+            draw_token_gizmos.run_if(in_state(Screen::Gameplay)),
+        );
+    }
+}
+
+// -----------------------------------------------
+// RECURSO / COMPONENTES
+// -----------------------------------------------
+
+#[derive(Resource, Reflect, Debug)]
+#[reflect(Resource)]
+pub struct CombatCoordinator {
+    /// Enemigos que pueden estar atacando a la vez
+    pub max_tokens: usize,
+    /// Segundos mínimos entre dar un token y el siguiente
+    pub grant_interval: f32,
+    pub holders: Vec<Entity>,
+    /// Persiguiendo y esperando turno, del más cercano al más lejano
+    pub waiting: Vec<Entity>,
+    grant_cooldown: f32,
+}
+
+impl Default for CombatCoordinator {
+    fn default() -> Self {
+        let mut coordinator = Self {
+            max_tokens: 0,
+            grant_interval: 0.0,
+            holders: Vec::new(),
+            waiting: Vec::new(),
+            grant_cooldown: 0.0,
+        };
+        coordinator.apply_difficulty(Difficulty::default());
+        coordinator
+    }
+}
+
+impl CombatCoordinator {
+    fn apply_difficulty(&mut self, difficulty: Difficulty) {
+        (self.max_tokens, self.grant_interval) = match difficulty {
+            Difficulty::Easy => (1, 1.5),
+            Difficulty::Normal => (2, 0.8),
+            Difficulty::Hard => (3, 0.3),
+        };
+    }
+}
+
+/// Este enemigo tiene permiso para atacar
+#[derive(Component)]
+#[component(storage = "SparseSet")]
+pub struct AttackToken {
+    held: f32,
+}
+
+/// Qué hace un enemigo que persigue sin token
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum WaitingTactic {
+    /// Da vueltas alrededor del jugador
+    Circle,
+    /// Se coloca a su espalda
+    Flank,
+    /// Se queda quieto a media distancia
+    Wait,
+}
+
+impl WaitingTactic {
+    /// Fija por enemigo, para que no cambien de idea cada frame
+    pub fn for_enemy(entity: Entity) -> Self {
+        match entity.to_bits() % 3 {
+            0 => WaitingTactic::Circle,
+            1 => WaitingTactic::Flank,
+            _ => WaitingTactic::Wait,
+        }
+    }
+}
+
+// -----------------------------------------------
+// SISTEMAS
+// -----------------------------------------------
+
+fn reset_coordinator(mut coordinator: ResMut<CombatCoordinator>, difficulty: Res<Difficulty>) {
+    *coordinator = CombatCoordinator::default();
+    coordinator.apply_difficulty(*difficulty);
+}
+
+fn sync_difficulty(difficulty: Res<Difficulty>, mut coordinator: ResMut<CombatCoordinator>) {
+    if difficulty.is_changed() {
+        coordinator.apply_difficulty(*difficulty);
+        info!("Tokens de ataque: {} ({:?})", coordinator.max_tokens, *difficulty);
+    }
+}
+
+pub(super) fn release_tokens(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut changes: MessageReader<EnemyStateChanged>,
+    mut holders: Query<(Entity, &EnemyBrain, &mut AttackToken)>,
+    mut coordinator: ResMut<CombatCoordinator>,
+) {
+    let mut released: Vec<Entity> = changes
+        .read()
+        .filter(|c| {
+            c.from == EnemyState::Recover
+                || matches!(c.to, EnemyState::Stunned | EnemyState::Search | EnemyState::Idle | EnemyState::Dead)
+        })
+        .map(|c| c.enemy)
+        .collect();
+
+    for (entity, brain, mut token) in holders.iter_mut() {
+        if brain.state == EnemyState::Chase {
+            token.held += time.delta_secs();
+            if token.held > TOKEN_TIMEOUT {
+                released.push(entity);
+            }
+        }
+    }
+
+    for entity in &released {
+        if let Ok(mut entity_commands) = commands.get_entity(*entity) {
+            entity_commands.remove::<AttackToken>();
+        }
+    }
+    // Los que ya no existen también lo sueltan
+    coordinator
+        .holders
+        .retain(|e| !released.contains(e) && holders.contains(*e));
+}
+
+pub(super) fn grant_tokens(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut coordinator: ResMut<CombatCoordinator>,
+    candidates: Query<
+        (Entity, &Transform, &EnemyBrain, &enemy_ai::EnemyBehaviour, &Perception),
+        (With<Enemy>, Without<AttackToken>),
+    >,
+    player: Single<&Transform, With<Player>>,
+) {
+    coordinator.grant_cooldown -= time.delta_secs();
+
+    let mut waiting: Vec<(Entity, f32)> = candidates
+        .iter()
+        .filter(|(_, _, brain, _, perception)| brain.state == EnemyState::Chase && perception.sees_player)
+        .map(|(entity, transform, _, behaviour, _)| {
+            let distance = transform.translation.distance(player.translation);
+            (entity, distance - behaviour.attack_range)
+        })
+        .collect();
+    waiting.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+    while coordinator.holders.len() < coordinator.max_tokens && coordinator.grant_cooldown <= 0.0 {
+        let Some(index) = waiting.iter().position(|(_, gap)| *gap <= ENGAGE_MARGIN) else { break; };
+        let (entity, _) = waiting.remove(index);
+        commands.entity(entity).insert(AttackToken { held: 0.0 });
+        coordinator.holders.push(entity);
+        coordinator.grant_cooldown = coordinator.grant_interval;
+    }
+
+    coordinator.waiting = waiting.into_iter().map(|(e, _)| e).collect();
+}
+
+#[cfg(feature = "dev")]
+fn draw_token_gizmos(
+    mut gizmos: Gizmos,
+    options: Res<UiDebugOptions>,
+    coordinator: Res<CombatCoordinator>,
+    transforms: Query<&GlobalTransform>,
+    player: Single<&Transform, With<Player>>,
+) {
+    if !options.enabled {
+        return;
+    }
+    let target = player.translation + Vec3::Y;
+    for (entities, color) in [
+        (&coordinator.holders, Color::srgb(1.0, 0.2, 0.1)),
+        (&coordinator.waiting, Color::srgb(0.3, 0.6, 1.0)),
+    ] {
+        for entity in entities {
+            if let Ok(transform) = transforms.get(*entity) {
+                gizmos.line(transform.translation() + Vec3::Y * 2.0, target, color);
+            }
+        }
+    }
+}
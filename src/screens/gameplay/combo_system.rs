@@ -1,4 +1,18 @@
+//! Fever slow-mo — cada N kills de racha el tiempo se ralentiza un momento
+//!
+//! La escala se aplica con ease-in / hold / ease-out a `Time<Virtual>`.
+//! Avian corre en el schedule fijo, así que el timestep de `Time<Fixed>` se
+//! escala igual: la física se ve a cámara lenta pero sigue dando los mismos
+//! pasos por segundo real, sin tirones. El audio baja de pitch y se filtra
+//! con un low-pass en proporción a la escala; al salir, cada sonido vuelve a
+//! su propio corte.
+
+use std::time::Duration;
+
 use bevy::prelude::*;
+use bevy_seedling::prelude::{EffectsQuery, LowPassNode, PlaybackSettings, SampleEffects};
+
+use crate::PausableSystems;
 use crate::screens::Screen;
 use super::hud::KillStreak;
 
@@ -6,66 +20,247 @@ pub struct ComboSystemPlugin;
 
 impl Plugin for ComboSystemPlugin {
     fn build(&self, app: &mut App) {
+        app.register_type::<FeverSlowMoSettings>();
+        app.init_resource::<FeverSlowMoSettings>();
         app.insert_resource(SlowMotionState::default());
         app.add_systems(
             Update,
             (
                 streak_tracking,
                 tick_slow_motion,
+                apply_slow_motion_to_audio,
             )
                 .chain()
+                .in_set(PausableSystems)
                 .run_if(in_state(Screen::Gameplay)),
         );
+        app.add_systems(OnExit(Screen::Gameplay), reset_slow_motion);
+    }
+}
+
+/// Parámetros del fever slow-mo, editables desde el inspector
+#[derive(Resource, Reflect, Clone)]
+#[reflect(Resource)]
+pub struct FeverSlowMoSettings {
+    pub enabled: bool,
+    /// Se activa cada vez que la racha cruza un múltiplo de esto
+    pub kills_per_trigger: u32,
+    /// Escala mínima de tiempo (1.0 = normal)
+    pub speed: f32,
+    /// Segundos reales de cada fase
+    pub ease_in: f32,
+    pub hold: f32,
+    pub ease_out: f32,
+    /// Frecuencia del low-pass en el punto más lento
+    pub low_pass_hz: f32,
+}
+
+impl Default for FeverSlowMoSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            kills_per_trigger: 3,
+            speed: 0.3,
+            ease_in: 0.08,
+            hold: 0.4,
+            ease_out: 0.3,
+            low_pass_hz: 800.0,
+        }
     }
 }
 
-#[derive(Resource, Default)]
+impl FeverSlowMoSettings {
+    fn duration(&self) -> f32 {
+        self.ease_in + self.hold + self.ease_out
+    }
+
+    /// Escala de tiempo `elapsed` segundos reales después de activarse
+    pub fn scale_at(&self, elapsed: f32) -> f32 {
+        let t = if elapsed < self.ease_in {
+            1.0 - elapsed / self.ease_in
+        } else if elapsed < self.ease_in + self.hold {
+            0.0
+        } else if elapsed < self.duration() {
+            (elapsed - self.ease_in - self.hold) / self.ease_out
+        } else {
+            return 1.0;
+        };
+        let eased = t * t * (3.0 - 2.0 * t);
+        self.speed + (1.0 - self.speed) * eased
+    }
+}
+
+#[derive(Resource)]
 pub struct SlowMotionState {
     pub active: bool,
-    pub timer: f32,
+    /// Segundos reales desde que empezó el slow motion
+    pub elapsed: f32,
+    /// Escala actual aplicada a `Time<Virtual>`
+    pub scale: f32,
     last_milestone: u32,
+    /// Timestep original de `Time<Fixed>` para restaurarlo al terminar
+    fixed_timestep: Option<Duration>,
+}
+
+impl Default for SlowMotionState {
+    fn default() -> Self {
+        Self {
+            active: false,
+            elapsed: 0.0,
+            scale: 1.0,
+            last_milestone: 0,
+            fixed_timestep: None,
+        }
+    }
 }
 
-// Detecta múltiplos de 3 kills y activa slow motion
+// Detecta múltiplos de N kills y activa slow motion
 fn streak_tracking(
     streak: Res<KillStreak>,
+    settings: Res<FeverSlowMoSettings>,
     mut slow_mo: ResMut<SlowMotionState>,
-    mut virtual_time: ResMut<Time<Virtual>>,
 ) {
     // Si el streak se resetea, resetear el milestone
     if streak.kills == 0 {
-        slow_mo.last_milestone = 0;
+        if slow_mo.last_milestone != 0 {
+            slow_mo.last_milestone = 0;
+        }
+        return;
+    }
+    if !settings.enabled || settings.kills_per_trigger == 0 {
         return;
     }
 
-    let current_milestone = (streak.kills / 3) * 3;
+    let per = settings.kills_per_trigger;
+    let current_milestone = (streak.kills / per) * per;
 
-    if current_milestone > slow_mo.last_milestone && streak.kills >= 3 {
-        // Activar slow motion
-        slow_mo.active = true;
-        slow_mo.timer = 0.4;
+    if current_milestone > slow_mo.last_milestone && streak.kills >= per {
         slow_mo.last_milestone = current_milestone;
-
-        // Ralentizar el tiempo a 30% de velocidad
-        virtual_time.set_relative_speed(0.3);
+        if slow_mo.active {
+            // Ya estamos lentos: alargar el hold sin volver a hacer ease-in
+            slow_mo.elapsed = slow_mo.elapsed.min(settings.ease_in);
+        } else {
+            slow_mo.active = true;
+            slow_mo.elapsed = 0.0;
+        }
     }
 }
 
-// Cuenta el tiempo de slow motion y restaura velocidad normal
-fn tick_slow_motion(
+// Avanza la curva y aplica la escala a Time<Virtual> y Time<Fixed>
+pub(super) fn tick_slow_motion(
     mut slow_mo: ResMut<SlowMotionState>,
+    settings: Res<FeverSlowMoSettings>,
     mut virtual_time: ResMut<Time<Virtual>>,
+    mut fixed_time: ResMut<Time<Fixed>>,
     time: Res<Time<Real>>,
 ) {
     if !slow_mo.active {
         return;
     }
 
-    slow_mo.timer -= time.delta_secs();
+    let base_timestep = *slow_mo.fixed_timestep.get_or_insert(fixed_time.timestep());
+
+    slow_mo.elapsed += time.delta_secs();
+    slow_mo.scale = settings.scale_at(slow_mo.elapsed);
 
-    if slow_mo.timer <= 0.0 {
+    if slow_mo.elapsed >= settings.duration() {
         slow_mo.active = false;
-        // Restaurar velocidad normal
-        virtual_time.set_relative_speed(1.0);
+        slow_mo.scale = 1.0;
+        slow_mo.fixed_timestep = None;
+    }
+
+    virtual_time.set_relative_speed(slow_mo.scale);
+    fixed_time.set_timestep(base_timestep.mul_f32(slow_mo.scale));
+}
+
+/// Corte del low-pass de un sonido antes del slow-mo, para volver a él
+#[derive(Component)]
+struct BaseCutoff(f32);
+
+// Pitch y low-pass de los sonidos que están sonando
+fn apply_slow_motion_to_audio(
+    mut commands: Commands,
+    slow_mo: Res<SlowMotionState>,
+    settings: Res<FeverSlowMoSettings>,
+    mut players: Query<(Entity, &mut PlaybackSettings, Option<&SampleEffects>, Option<&BaseCutoff>)>,
+    mut low_passes: Query<&mut LowPassNode>,
+) {
+    if !slow_mo.is_changed() {
+        return;
+    }
+
+    let scale = slow_mo.scale;
+    // Interpolación en log: 1.0 → sin filtro audible, speed → low_pass_hz
+    let depth = ((1.0 - scale) / (1.0 - settings.speed).max(f32::EPSILON)).clamp(0.0, 1.0);
+    let cutoff = 20_000.0_f32.powf(1.0 - depth) * settings.low_pass_hz.powf(depth);
+
+    for (entity, mut playback, effects, base) in players.iter_mut() {
+        playback.speed = scale as f64;
+        if let Some(effects) = effects
+            && let Ok(mut low_pass) = low_passes.get_effect_mut(effects)
+        {
+            // La primera vez que se ve, el corte aún es el suyo
+            let base = match base {
+                Some(base) => base.0,
+                None => {
+                    commands.entity(entity).insert(BaseCutoff(low_pass.frequency));
+                    low_pass.frequency
+                }
+            };
+            low_pass.frequency = base.min(cutoff);
+        }
+    }
+}
+
+fn reset_slow_motion(
+    mut slow_mo: ResMut<SlowMotionState>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    mut fixed_time: ResMut<Time<Fixed>>,
+) {
+    if let Some(timestep) = slow_mo.fixed_timestep {
+        fixed_time.set_timestep(timestep);
+    }
+    virtual_time.set_relative_speed(1.0);
+    *slow_mo = SlowMotionState::default();
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::ecs::system::RunSystemOnce;
+
+    use super::*;
+
+    #[test]
+    fn curve_starts_and_ends_at_normal_speed() {
+        let settings = FeverSlowMoSettings::default();
+        assert!((settings.scale_at(0.0) - 1.0).abs() < 1e-6);
+        assert_eq!(settings.scale_at(settings.ease_in + settings.hold * 0.5), settings.speed);
+        assert_eq!(settings.scale_at(settings.duration()), 1.0);
+    }
+
+    #[test]
+    fn time_returns_to_normal_after_fever() {
+        let settings = FeverSlowMoSettings::default();
+        let mut world = World::new();
+        world.insert_resource(settings.clone());
+        world.insert_resource(SlowMotionState { active: true, ..default() });
+        world.insert_resource(Time::<Virtual>::default());
+        world.insert_resource(Time::<Fixed>::default());
+        world.insert_resource(Time::<Real>::default());
+        let base_timestep = world.resource::<Time<Fixed>>().timestep();
+
+        let step = Duration::from_secs_f32(1.0 / 60.0);
+        let frames = (settings.duration() / step.as_secs_f32()).ceil() as usize + 5;
+        let mut slowest = 1.0_f32;
+        for _ in 0..frames {
+            world.resource_mut::<Time<Real>>().advance_by(step);
+            world.run_system_once(tick_slow_motion).unwrap();
+            slowest = slowest.min(world.resource::<Time<Virtual>>().relative_speed());
+        }
+
+        assert!(slowest < 0.5, "nunca se ralentizó: {slowest}");
+        assert!(!world.resource::<SlowMotionState>().active);
+        assert_eq!(world.resource::<Time<Virtual>>().relative_speed(), 1.0);
+        assert_eq!(world.resource::<Time<Fixed>>().timestep(), base_timestep);
     }
 }
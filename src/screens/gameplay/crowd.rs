@@ -0,0 +1,139 @@
+//! Separación entre enemigos
+//!
+//! Con navmesh, landmass ya hace avoidance local entre agentes (ver las
+//! opciones del archipiélago en `gameplay/mod.rs`), pero mientras se genera
+//! el navmesh y en los estados sin camino todos van en línea recta hacia el
+//! mismo punto. Aquí se añade:
+//!
+//! - Steering de separación: cada enemigo se aparta de los vecinos cercanos.
+//! - Resolución de solapes: `enemy_collision` solo empuja contra geometría, y
+//!   dos cuerpos kinematic no se empujan entre sí, así que se separan a mano.
+//!
+//! Los vecinos se buscan en una rejilla para que escale con muchos enemigos.
+
+use avian3d::prelude::*;
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+
+use crate::screens::Screen;
+use crate::screens::gameplay::{
+    ai_lod::{AiLod, LodLevel},
+    charge_attack::{self, Charging},
+    enemy::{Enemy, Knockback},
+    enemy_ai::{self, EnemyBrain, EnemyState},
+};
+
+pub struct CrowdPlugin;
+
+/// A esta distancia entre centros empiezan a apartarse
+const SEPARATION_RADIUS: f32 = 2.0;
+/// Velocidad máxima que añade la separación
+const SEPARATION_SPEED: f32 = 2.5;
+/// Radio del collider del enemigo
+const BODY_RADIUS: f32 = 0.45;
+
+impl Plugin for CrowdPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            separation_steering
+                .after(enemy_ai::enemy_locomotion)
+                .before(charge_attack::charge_tick)
+                .run_if(in_state(Screen::Gameplay)),
+        );
+        app.add_systems(
+            PhysicsSchedule,
+            resolve_enemy_overlaps.in_set(NarrowPhaseSystems::Last),
+        );
+    }
+}
+
+/// Rejilla de celdas de `SEPARATION_RADIUS` en XZ
+fn build_grid(positions: &[Vec3]) -> HashMap<IVec2, Vec<usize>> {
+    let mut grid: HashMap<IVec2, Vec<usize>> = HashMap::default();
+    for (i, position) in positions.iter().enumerate() {
+        grid.entry(cell(*position)).or_default().push(i);
+    }
+    grid
+}
+
+fn cell(position: Vec3) -> IVec2 {
+    (position.xz() / SEPARATION_RADIUS).floor().as_ivec2()
+}
+
+/// Índices de los que están en la celda de `position` o en las de alrededor
+fn neighbours<'a>(grid: &'a HashMap<IVec2, Vec<usize>>, position: Vec3) -> impl Iterator<Item = usize> + 'a {
+    let center = cell(position);
+    (-1..=1)
+        .flat_map(move |x| (-1..=1).map(move |y| center + IVec2::new(x, y)))
+        .filter_map(|c| grid.get(&c))
+        .flatten()
+        .copied()
+}
+
+pub(super) fn separation_steering(
+    mut enemies: Query<
+        (&Transform, &EnemyBrain, &mut LinearVelocity, Option<&AiLod>),
+        (With<Enemy>, Without<Charging>, Without<Knockback>),
+    >,
+) {
+    let positions: Vec<Vec3> = enemies.iter().map(|(t, ..)| t.translation).collect();
+    let grid = build_grid(&positions);
+
+    for (i, (transform, brain, mut linear_velocity, lod)) in enemies.iter_mut().enumerate() {
+        // Quietos a propósito: no se les empuja por steering
+        if matches!(brain.state, EnemyState::Windup | EnemyState::Stunned | EnemyState::Dead)
+            || lod.is_some_and(|lod| lod.level == LodLevel::Asleep)
+        {
+            continue;
+        }
+        let mut push = Vec3::ZERO;
+        for j in neighbours(&grid, transform.translation) {
+            if i == j {
+                continue;
+            }
+            let away = (transform.translation - positions[j]).with_y(0.0);
+            let distance = away.length();
+            if distance >= SEPARATION_RADIUS {
+                continue;
+            }
+            // Uno encima de otro: se aparta hacia un lado cualquiera pero fijo
+            let dir = if distance > 0.001 { away / distance } else if i < j { Vec3::X } else { Vec3::NEG_X };
+            push += dir * (1.0 - distance / SEPARATION_RADIUS);
+        }
+        let push = push.clamp_length_max(1.0) * SEPARATION_SPEED;
+        linear_velocity.x += push.x;
+        linear_velocity.z += push.z;
+    }
+}
+
+/// Kinematic contra kinematic: cada uno se lleva la mitad de la penetración
+fn resolve_enemy_overlaps(mut enemies: Query<&mut Position, With<Enemy>>) {
+    let positions: Vec<Vec3> = enemies.iter().map(|p| p.0).collect();
+    let grid = build_grid(&positions);
+    let min_distance = BODY_RADIUS * 2.0;
+
+    let mut corrections = vec![Vec3::ZERO; positions.len()];
+    for (i, position) in positions.iter().enumerate() {
+        for j in neighbours(&grid, *position) {
+            if j <= i {
+                continue;
+            }
+            let offset = (*position - positions[j]).with_y(0.0);
+            let distance = offset.length();
+            if distance >= min_distance {
+                continue;
+            }
+            let dir = if distance > 0.001 { offset / distance } else { Vec3::X };
+            let half = dir * (min_distance - distance) * 0.5;
+            corrections[i] += half;
+            corrections[j] -= half;
+        }
+    }
+
+    for (mut position, correction) in enemies.iter_mut().zip(corrections) {
+        if correction != Vec3::ZERO {
+            position.0 += correction;
+        }
+    }
+}
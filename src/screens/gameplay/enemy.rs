@@ -3,43 +3,65 @@ use std::time::Duration;
 
 use avian3d::{math::*, prelude::*};
 use bevy::prelude::*;
+use bevy_landmass::prelude::*;
+use bevy_seedling::sample::AudioSample;
 use crate::screens::Screen;
-use crate::screens::gameplay::LevelAssets;
-use crate::screens::gameplay::hammerhead::HammerheadAssets;
-use crate::screens::gameplay::alarm_clock::FrozenEnemy;
+use crate::screens::gameplay::{LevelAssets, NavmeshArchipelagoHolder, NavmeshDone};
+use crate::screens::gameplay::boss::Boss;
+use crate::screens::gameplay::poise::Poise;
+use crate::screens::gameplay::charge_attack;
+use crate::screens::gameplay::perception::{self, Noise, Perception};
+use crate::screens::gameplay::enemy_ai::{
+    self, EnemyBehaviour, EnemyBrain, EnemyState, EnemyStateChanged,
+};
+use crate::screens::gameplay::events::{DeathCause, EnemyKilledEvent};
+use crate::screens::gameplay::enemy_archetype::{EnemyArchetype, EnemyArchetypeHandle, EnemyArchetypes, EnemyMovement};
+use crate::screens::gameplay::flying_enemy::Flying;
+use crate::screens::gameplay::enemy_death::Dying;
+use crate::screens::gameplay::health_bar;
+use crate::screens::gameplay::ledge::LastHitBy;
+use crate::screens::gameplay::loot::LootTableId;
 
 pub struct EnemyPlugin;
 
 const ENEMY_GRAVITY: Vec3 = Vec3::new(0.0, -9.81, 0.0);
-const MAX_HEALTH: u32 = 3;
-const ENEMY_SPEED: f32 = 3.0;
-const DETECTION_RANGE: f32 = 20.0;
-const ATTACK_RANGE: f32 = 2.2;
-const ATTACK_DAMAGE: f32 = 0.25;
-const ATTACK_COOLDOWN: f32 = 5.0;
-
-#[derive(Component)]
-pub struct HealthText;
-
-#[derive(Component)]
-pub struct EnemyHealthBillboard {
-    pub enemy: Entity,
-}
+/// Si el destino se mueve más que esto se recalcula el camino de los agentes
+const REPATH_DISTANCE: f32 = 1.5;
+/// Por debajo de esta altura el enemigo se da por caído al vacío
+const ENEMY_VOID_Y: f32 = -50.0;
+/// Tanto por debajo del último suelo que pisó también es vacío
+const ENEMY_VOID_DEPTH: f32 = 25.0;
 
 static ENEMY_ID_COUNTER: AtomicU32 = AtomicU32::new(1);
 
 impl Plugin for EnemyPlugin {
     fn build(&self, app: &mut App) {
+        app.add_message::<EnemyKilledEvent>();
+        app.add_message::<EnemyStateChanged>();
+        app.add_message::<Noise>();
+        app.register_type::<EnemyBrain>();
+        app.register_type::<EnemyBehaviour>();
+        app.init_resource::<ChaseTarget>();
+        app.init_resource::<enemy_ai::WindupTelegraphAssets>();
         app.add_systems(
             Update,
             (
                 setup_enemy_animations,
-                enemy_chase_and_attack,
+                perception::player_noise,
+                perception::update_sight,
+                perception::update_hearing,
+                enemy_ai::update_enemy_brains,
+                enemy_ai::enemy_state_hooks,
+                update_chase_target,
+                enemy_ai::enemy_locomotion,
+                charge_attack::charge_tick,
+                charge_attack::charge_impact,
+                enemy_ai::windup_telegraph_tick,
                 apply_knockback,
                 update_grounded,
                 apply_gravity,
                 enemy_health_system,
-                sync_billboard_position,
+                sync_agent_velocity,
             )
                 .chain()
                 .run_if(in_state(Screen::Gameplay)),
@@ -56,14 +78,36 @@ impl Plugin for EnemyPlugin {
 pub struct Enemy {
     pub id: u32,
     pub health: u32,
+    pub max_health: u32,
     pub attack_cooldown: f32,
 }
 
+/// Reproductor de animación de un enemigo (un hijo de su escena)
 #[derive(Component)]
+#[relationship(relationship_target = EnemyAnimationLink)]
 pub struct EnemyAnimationPlayer {
     pub enemy: Entity,
 }
 
+/// Enlace directo del enemigo a su reproductor, para no buscarlo entre todos
+#[derive(Component)]
+#[relationship_target(relationship = EnemyAnimationPlayer)]
+pub struct EnemyAnimationLink(Entity);
+
+impl EnemyAnimationLink {
+    pub fn player(&self) -> Entity {
+        self.0
+    }
+}
+
+/// Nodos del grafo de animación del enemigo, en el orden de sus clips
+#[derive(Component)]
+pub struct EnemyAnimations {
+    pub nodes: Vec<AnimationNodeIndex>,
+    /// Velocidad que pide el estado actual (antes de la dilatación temporal)
+    pub speed: f32,
+}
+
 #[derive(Component)]
 pub struct Knockback {
     pub velocity: Vec3,
@@ -74,9 +118,22 @@ pub struct Knockback {
 #[component(storage = "SparseSet")]
 pub struct Grounded;
 
+/// Último sitio donde el enemigo tuvo suelo bajo los pies
+#[derive(Component, Default)]
+pub struct LastGroundedPosition(pub Vec3);
+
+/// Sonidos al golpear y al matar a este enemigo
+#[derive(Component)]
+pub struct EnemySounds {
+    pub hit: Handle<AudioSample>,
+    pub death: Handle<AudioSample>,
+}
+
 pub struct EnemySpawnCmd {
     pub transform: Transform,
     pub parent: Option<Entity>,
+    /// Nombre del tipo de enemigo (`EnemySpawn.r#type`); vacío = el de por defecto
+    pub archetype: String,
 }
 
 impl Command for EnemySpawnCmd {
@@ -89,56 +146,111 @@ pub fn spawn_enemy(
     In(args): In<EnemySpawnCmd>,
     mut c: Commands,
     level_assets: Res<LevelAssets>,
-) {
+    archetypes: Res<EnemyArchetypes>,
+    archetype_assets: Res<Assets<EnemyArchetype>>,
+    archipelago: Option<Res<NavmeshArchipelagoHolder>>,
+) -> Option<Entity> {
+    let (archetype_handle, archetype) = match archetypes.get(&args.archetype, &archetype_assets) {
+        Ok(found) => found,
+        Err(err) => {
+            error!("No se pudo spawnear enemigo en {}: {err}", args.transform.translation);
+            return None;
+        }
+    };
     let enemy_id = ENEMY_ID_COUNTER.fetch_add(1, Ordering::Relaxed);
+    let def = &archetype.def;
+    let behaviour = def.behaviour.clone();
+    let mut transform = args.transform;
+    transform.translation.y += def.spawn.spawn_height;
+    transform.scale = Vec3::splat(def.scale);
 
-    let enemy_collider = Collider::capsule(0.45, 1.3);
+    let enemy_collider = Collider::capsule(def.collider.radius, def.collider.length);
     let mut caster_shape = enemy_collider.clone();
     caster_shape.set_scale(Vec3::ONE * 0.99, 10);
 
     let enemy_entity = c.spawn((
-        Name::new(format!("Enemy_{}", enemy_id)),
+        Name::new(format!("Enemy_{}_{}", def.name, enemy_id)),
         Enemy {
             id: enemy_id,
-            health: MAX_HEALTH,
+            health: def.health,
+            max_health: def.health,
             attack_cooldown: 0.0,
         },
-        SceneRoot(level_assets.hammerhead.scene.clone()),
-        args.transform,
+        EnemyBrain::new(transform.translation),
+        Perception::default(),
+        LastGroundedPosition(args.transform.translation),
+        EnemySounds {
+            hit: archetype.hit_sound.clone().unwrap_or_else(|| level_assets.hit_enemy_first.clone()),
+            death: archetype.death_sound.clone().unwrap_or_else(|| level_assets.hit_enemy_final.clone()),
+        },
+        SceneRoot(archetype.scene.clone()),
+        transform,
         Visibility::Inherited,
         RigidBody::Kinematic,
         LinearVelocity::default(),
-        ShapeCaster::new(
-            caster_shape,
-            Vec3::new(0.0, 1.17, 0.0),
-            Quaternion::default(),
-            Dir3::NEG_Y,
-        )
-        .with_max_distance(0.5),
+        // Al reiniciar se lleva también su barra de vida y su cuerpo si está muriendo
+        DespawnOnExit(Screen::Gameplay),
     ))
     .with_children(|parent| {
         parent.spawn((
-            Collider::capsule(0.45, 1.3),
-            Transform::from_xyz(0.0, 1.17, 0.0),
+            enemy_collider,
+            Transform::from_xyz(0.0, def.collider.height, 0.0),
+            // Para que la embestida sepa cuándo toca al jugador
+            CollisionEventsEnabled,
         ));
     })
     .id();
 
-    c.spawn((
-        Name::new(format!("HealthBillboard_{}", enemy_id)),
-        EnemyHealthBillboard { enemy: enemy_entity },
-        HealthText,
-        Text::new("XXX"),
-        TextFont { font_size: 20.0, ..default() },
-        TextColor(bevy::color::palettes::css::RED.into()),
-        Node {
-            position_type: PositionType::Absolute,
-            left: Val::Px(0.0),
-            top: Val::Px(0.0),
-            ..default()
-        },
-        ZIndex(10),
+    match &def.movement {
+        // Los voladores no usan navmesh ni detectan suelo con el caster
+        EnemyMovement::Flying(flight) => {
+            c.entity(enemy_entity).insert(Flying::new(flight.clone(), transform.translation));
+        }
+        EnemyMovement::Ground => {
+            c.entity(enemy_entity).insert(
+                ShapeCaster::new(
+                    caster_shape,
+                    Vec3::new(0.0, def.collider.height, 0.0),
+                    Quaternion::default(),
+                    Dir3::NEG_Y,
+                )
+                .with_max_distance(0.5),
+            );
+        }
+    }
+
+    // Agente de landmass: el navmesh decide por dónde ir
+    if let Some(archipelago) = archipelago
+        && matches!(def.movement, EnemyMovement::Ground)
+    {
+        c.entity(enemy_entity).insert((
+            Agent3dBundle {
+                agent: Default::default(),
+                settings: AgentSettings {
+                    radius: def.collider.radius + 0.05,
+                    desired_speed: behaviour.chase_speed,
+                    max_speed: behaviour.chase_speed * 1.3,
+                },
+                archipelago_ref: ArchipelagoRef3d::new(archipelago.0),
+            },
+            AgentTarget3d::None,
+            Velocity3d::default(),
+        ));
+    }
+    if let Some(loot) = def.loot {
+        c.entity(enemy_entity).insert(loot);
+    }
+    if let Some(boss) = &def.boss {
+        c.entity(enemy_entity).insert(Boss::new(boss.clone()));
+    }
+    c.entity(enemy_entity).insert((
+        behaviour,
+        Poise::new(def.poise.clone()),
+        EnemyArchetypeHandle(archetype_handle),
     ));
+
+    health_bar::spawn_health_bar(&mut c, enemy_entity, format!("HealthBar_{}", enemy_id));
+    Some(enemy_entity)
 }
 
 // -----------------------------------------------
@@ -147,105 +259,120 @@ pub fn spawn_enemy(
 
 fn setup_enemy_animations(
     mut commands: Commands,
-    new_players: Query<(Entity, &ChildOf), (Added<AnimationPlayer>, Without<EnemyAnimationPlayer>)>,
-    enemies: Query<Entity, With<Enemy>>,
-    level_assets: Res<LevelAssets>,
-    mut graphs: ResMut<Assets<AnimationGraph>>,
+    mut new_players: Query<
+        (Entity, &ChildOf, &mut AnimationPlayer),
+        (Added<AnimationPlayer>, Without<EnemyAnimationPlayer>),
+    >,
+    enemies: Query<(&EnemyBrain, &EnemyBehaviour, &EnemyArchetypeHandle), With<Enemy>>,
+    archetypes: Res<Assets<EnemyArchetype>>,
 ) {
-    for (anim_entity, child_of) in new_players.iter() {
-        if enemies.get(child_of.0).is_ok() {
+    for (anim_entity, child_of, mut player) in new_players.iter_mut() {
+        if let Ok((brain, behaviour, archetype)) = enemies.get(child_of.0)
+            && let Some(archetype) = archetypes.get(&archetype.0)
+        {
             let enemy_entity = child_of.0;
 
-            let (graph, _node_indices) = AnimationGraph::from_clips(
-                level_assets.hammerhead.animations.clone()
-            );
-            let graph_handle = graphs.add(graph);
+            // El grafo es del tipo de enemigo, compartido entre todos
+            let node_indices = archetype.animation_nodes.clone();
+            // Arranca con la animación del estado en el que ya esté
+            let mut transitions = AnimationTransitions::new();
+            let mut speed = 1.0;
+            if let Some(animation) = behaviour.animation(brain.state)
+                && let Some(&node) = node_indices.get(animation.clip)
+            {
+                speed = animation.speed;
+                let active = transitions.play(&mut player, node, Duration::ZERO).set_speed(speed);
+                if animation.repeat {
+                    active.repeat();
+                }
+            }
 
             commands.entity(anim_entity).insert((
                 EnemyAnimationPlayer { enemy: enemy_entity },
-                AnimationGraphHandle(graph_handle),
+                EnemyAnimations { nodes: node_indices, speed },
+                AnimationGraphHandle(archetype.graph.clone()),
+                transitions,
             ));
 
-            info!("Enemy animation player linked to Enemy {:?}", enemy_entity);
+            debug!("Enemy animation player linked to Enemy {:?}", enemy_entity);
         }
     }
 }
 
-fn enemy_chase_and_attack(
-    mut enemies: Query<
-        (Entity, &Transform, &mut LinearVelocity, &mut Rotation, &mut Enemy),
-        Without<FrozenEnemy>,
-    >,
-    mut players: Query<(&Transform, &mut super::player::Player), With<super::Player>>,
-    mut anim_players: Query<(&EnemyAnimationPlayer, &mut AnimationPlayer, &mut AnimationTransitions)>,
-    _level_assets: Res<LevelAssets>,
-    _graphs: ResMut<Assets<AnimationGraph>>,
-    time: Res<Time>,
-) {
-    const ANIM_ATTACK: usize = 0;
-    const ANIM_IDLE: usize = 1;
-    const ANIM_RUN: usize = 2;
-
-    let Ok((player_transform, mut player)) = players.single_mut() else { return; };
-    let player_pos = player_transform.translation;
-
-    for (enemy_entity, enemy_transform, mut linear_velocity, mut rotation, mut enemy) in enemies.iter_mut() {
-        let enemy_pos = enemy_transform.translation;
-        let dist = enemy_pos.distance(player_pos);
-
-        enemy.attack_cooldown -= time.delta_secs();
+// -----------------------------------------------
+// NAVEGACIÓN
+// -----------------------------------------------
 
-        let anim = anim_players.iter_mut()
-            .find(|(ap, _, _)| ap.enemy == enemy_entity);
+/// Punto del navmesh donde está el jugador y la isla en la que está
+#[derive(Resource, Default)]
+struct ChaseTarget {
+    point: Option<Vec3>,
+    island: Option<Entity>,
+}
 
-        if dist > DETECTION_RANGE {
-            linear_velocity.x = 0.0;
-            linear_velocity.z = 0.0;
-            if let Some((_, mut player, mut transitions)) = anim {
-                if !player.is_playing_animation(AnimationNodeIndex::new(ANIM_IDLE + 1)) {
-                    transitions.play(&mut player, AnimationNodeIndex::new(ANIM_IDLE + 1), Duration::from_millis(300)).repeat();
-                }
-            }
-            continue;
+/// Proyecta al jugador sobre el navmesh y repathea a los agentes cuando el
+/// destino se mueve lo suficiente o el jugador salta a otra isla. Quien lo
+/// ve va a por el jugador; quien lo ha perdido, a su última posición conocida.
+fn update_chase_target(
+    navmesh_done: Res<NavmeshDone>,
+    archipelago: Option<Res<NavmeshArchipelagoHolder>>,
+    archipelagos: Query<&Archipelago3d>,
+    player: Single<&Transform, With<super::Player>>,
+    mut target: ResMut<ChaseTarget>,
+    mut agents: Query<(&EnemyBrain, &Perception, &mut AgentTarget3d), With<Enemy>>,
+) {
+    if !navmesh_done.0 { return; }
+    let Some(archipelago) = archipelago else { return; };
+    let Ok(archipelago) = archipelagos.get(archipelago.0) else { return; };
+
+    // En el aire o fuera del navmesh se mantiene el último punto
+    let mut island_changed = false;
+    if let Ok(sampled) = archipelago.sample_point(
+        player.translation,
+        &PointSampleDistance3d {
+            horizontal_distance: 1.0,
+            distance_above: 2.0,
+            distance_below: 2.0,
+            vertical_preference_ratio: 1.0,
+        },
+    ) {
+        let island = sampled.island();
+        island_changed = target.island.is_some_and(|i| i != island);
+        if island_changed {
+            info!("Jugador en otra isla ({:?}), repath de enemigos", island);
         }
+        target.point = Some(sampled.point());
+        target.island = Some(island);
+    }
 
-        if dist <= ATTACK_RANGE {
-            linear_velocity.x = 0.0;
-            linear_velocity.z = 0.0;
-
-            if enemy.attack_cooldown <= 0.0 {
-                enemy.attack_cooldown = ATTACK_COOLDOWN;
-                player.health = (player.health - ATTACK_DAMAGE).max(0.0);
-                info!("Enemy atacó al jugador! Player health: {:.2}", player.health);
-
-                if let Some((_, mut anim_player, mut transitions)) = anim {
-                    transitions.play(&mut anim_player, AnimationNodeIndex::new(ANIM_ATTACK + 1), Duration::from_millis(100));
-                }
-            } else {
-                if let Some((_, mut anim_player, mut transitions)) = anim {
-                    if !anim_player.is_playing_animation(AnimationNodeIndex::new(ANIM_IDLE + 1)) {
-                        transitions.play(&mut anim_player, AnimationNodeIndex::new(ANIM_IDLE + 1), Duration::from_millis(300)).repeat();
-                    }
-                }
-            }
-        } else {
-            let dir = Vec3::new(player_pos.x - enemy_pos.x, 0.0, player_pos.z - enemy_pos.z).normalize_or_zero();
-            linear_velocity.x = dir.x * ENEMY_SPEED;
-            linear_velocity.z = dir.z * ENEMY_SPEED;
-
-            if dir.length_squared() > 0.001 {
-                *rotation = Quat::from_rotation_y((-dir.x).atan2(-dir.z)).into();
-            }
-
-            if let Some((_, mut anim_player, mut transitions)) = anim {
-                if !anim_player.is_playing_animation(AnimationNodeIndex::new(ANIM_RUN + 1)) {
-                    transitions.play(&mut anim_player, AnimationNodeIndex::new(ANIM_RUN + 1), Duration::from_millis(200)).repeat();
-                }
-            }
+    for (brain, perception, mut agent_target) in agents.iter_mut() {
+        let wanted = match brain.state {
+            EnemyState::Chase if perception.sees_player => target.point,
+            EnemyState::Chase | EnemyState::Search => perception.last_known,
+            _ => None,
+        };
+        let current = match *agent_target {
+            AgentTarget3d::Point(point) => Some(point),
+            _ => None,
+        };
+        let repath = match (wanted, current) {
+            (Some(wanted), Some(current)) => island_changed || wanted.distance(current) > REPATH_DISTANCE,
+            (None, None) => false,
+            _ => true,
+        };
+        if repath {
+            *agent_target = wanted.map_or(AgentTarget3d::None, AgentTarget3d::Point);
         }
     }
 }
 
+/// landmass necesita la velocidad real del agente para la evitación
+fn sync_agent_velocity(mut agents: Query<(&LinearVelocity, &mut Velocity3d), With<Enemy>>) {
+    for (linear_velocity, mut velocity) in agents.iter_mut() {
+        velocity.velocity = linear_velocity.0;
+    }
+}
+
 // -----------------------------------------------
 // FÍSICA
 // -----------------------------------------------
@@ -267,13 +394,17 @@ fn apply_knockback(
 
 fn update_grounded(
     mut commands: Commands,
-    mut query: Query<(Entity, &ShapeHits, &Rotation), (With<Enemy>, Without<Knockback>)>,
+    mut query: Query<
+        (Entity, &ShapeHits, &Rotation, &Transform, &mut LastGroundedPosition),
+        (With<Enemy>, Without<Knockback>),
+    >,
 ) {
-    for (entity, hits, rotation) in &mut query {
+    for (entity, hits, rotation, transform, mut last_grounded) in &mut query {
         let is_grounded = hits.iter().any(|hit| {
             (rotation * -hit.normal2).angle_between(Vector::Y).abs() <= 35f32.to_radians()
         });
         if is_grounded {
+            last_grounded.0 = transform.translation;
             commands.entity(entity).insert(Grounded);
         } else {
             commands.entity(entity).remove::<Grounded>();
@@ -283,45 +414,63 @@ fn update_grounded(
 
 fn apply_gravity(
     time: Res<Time>,
-    mut enemies: Query<&mut LinearVelocity, (With<Enemy>, Without<Knockback>, Without<Grounded>)>,
+    mut enemies: Query<&mut LinearVelocity, (With<Enemy>, Without<Knockback>, Without<Grounded>, Without<Flying>)>,
 ) {
     for mut linear_velocity in enemies.iter_mut() {
         linear_velocity.0 += ENEMY_GRAVITY * time.delta_secs();
     }
 }
 
+/// Único sitio donde muere un enemigo. Golpes, caídas o cualquier cosa que
+/// lo deje sin vida acaban aquí y se anuncia con `EnemyKilledEvent`. En
+/// combate el cuerpo pasa a `Dying` (ver `enemy_death`); al vacío se va sin
+/// más, y su barra de vida y sus partículas con él. La muerte es de quien le
+/// dio el último golpe (`LastHitBy`), también si solo lo tiró de la nube.
 fn enemy_health_system(
     mut commands: Commands,
-    level_assets: Res<LevelAssets>,
-    enemies: Query<(Entity, &Enemy, &Transform)>,
-    billboards: Query<(Entity, &EnemyHealthBillboard)>,
+    enemies: Query<(
+        Entity,
+        &Enemy,
+        &Transform,
+        &LastGroundedPosition,
+        Option<&LootTableId>,
+        Option<&EnemyArchetypeHandle>,
+        Option<&LastHitBy>,
+    )>,
+    archetypes: Res<Assets<EnemyArchetype>>,
+    mut killed_writer: MessageWriter<EnemyKilledEvent>,
 ) {
-    for (entity, enemy, transform) in enemies.iter() {
-        if enemy.health == 0 {
-            for (billboard_entity, billboard) in billboards.iter() {
-                if billboard.enemy == entity {
-                    commands.entity(billboard_entity).despawn();
-                }
-            }
-            commands.entity(entity).despawn();
-        }
-    }
-}
+    for (entity, enemy, transform, last_grounded, loot, archetype, last_hit) in enemies.iter() {
+        let cause = if enemy.health == 0 {
+            DeathCause::Combat
+        } else if transform.translation.y < ENEMY_VOID_Y
+            || transform.translation.y < last_grounded.0.y - ENEMY_VOID_DEPTH
+        {
+            DeathCause::Fall
+        } else {
+            continue;
+        };
+
+        killed_writer.write(EnemyKilledEvent {
+            enemy: entity,
+            position: match cause {
+                DeathCause::Combat => transform.translation,
+                DeathCause::Fall => last_grounded.0,
+            },
+            cause,
+            killer: last_hit.map(|hit| hit.attacker),
+            loot: loot.copied(),
+        });
 
-fn sync_billboard_position(
-    enemies: Query<&Transform, With<Enemy>>,
-    mut billboards: Query<(&EnemyHealthBillboard, &mut Node)>,
-    camera: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
-) {
-    let Ok((camera, cam_global)) = camera.single() else { return; };
-    for (billboard, mut node) in billboards.iter_mut() {
-        if let Ok(enemy_transform) = enemies.get(billboard.enemy) {
-            if enemy_transform.translation.y < -50.0 { continue; }
-            let world_pos = enemy_transform.translation + Vec3::new(0.0, 3.2, 0.0);
-            if let Ok(screen_pos) = camera.world_to_viewport(cam_global, world_pos) {
-                node.left = Val::Px(screen_pos.x - 20.0);
-                node.top = Val::Px(screen_pos.y - 20.0);
+        match cause {
+            DeathCause::Combat => {
+                let settings = archetype
+                    .and_then(|handle| archetypes.get(&handle.0))
+                    .map(|archetype| archetype.def.death.clone())
+                    .unwrap_or_default();
+                commands.entity(entity).insert(Dying::new(settings));
             }
+            DeathCause::Fall => commands.entity(entity).despawn(),
         }
     }
 }
@@ -0,0 +1,746 @@
+//! Máquina de estados de los enemigos
+//!
+//! Idle → Patrol → (ve u oye al jugador) → Alert → Chase → Windup → Attack →
+//! Recover → Chase... Si lo pierde de vista va a su última posición conocida
+//! y lo busca (Search). Un golpe lo manda a Stunned y la vida a 0 a Dead.
+//!
+//! `next_state` es una función pura: solo decide la transición. Los efectos
+//! (animaciones, telegraph del windup, daño) van en los hooks de entrada y
+//! salida, que reaccionan a `EnemyStateChanged`. Todos los tiempos, rangos y
+//! animaciones salen del `EnemyBehaviour` de cada tipo de enemigo (su
+//! archivo `.enemy.ron`, ver `enemy_archetype`).
+
+use std::time::Duration;
+
+use avian3d::prelude::*;
+use bevy::prelude::*;
+use bevy_landmass::prelude::*;
+use rand::RngExt;
+use serde::Deserialize;
+
+use crate::screens::gameplay::{
+    ai_lod::{self, AiLod, LodLevel},
+    NavmeshDone,
+    alarm_clock::TimeDilation,
+    charge_attack::{ChargeOutcome, Charging},
+    combat_coordinator::{AttackToken, WaitingTactic},
+    enemy::{Enemy, EnemyAnimationLink, EnemyAnimations},
+    flying_enemy::Flying,
+    hitstop::MeleeImpact,
+    perception::{self, Noise, Perception, PerceptionSettings},
+    player::Player,
+};
+
+// -----------------------------------------------
+// ESTADOS
+// -----------------------------------------------
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Default, Reflect, Deserialize)]
+pub enum EnemyState {
+    #[default]
+    Idle,
+    Patrol,
+    Alert,
+    Chase,
+    Search,
+    Windup,
+    Attack,
+    Recover,
+    Stunned,
+    Dead,
+}
+
+#[derive(Component, Reflect)]
+#[reflect(Component)]
+pub struct EnemyBrain {
+    pub state: EnemyState,
+    pub time_in_state: f32,
+    /// Centro de la patrulla (donde apareció)
+    pub home: Vec3,
+    /// Cuánto dura el stun actual (chocar contra una pared aturde más)
+    pub stun_time: f32,
+    patrol_target: Option<Vec3>,
+}
+
+impl EnemyBrain {
+    pub fn new(home: Vec3) -> Self {
+        Self {
+            state: EnemyState::Idle,
+            time_in_state: 0.0,
+            home,
+            stun_time: 0.0,
+            patrol_target: None,
+        }
+    }
+}
+
+#[derive(Message, Clone, Copy, Debug)]
+pub struct EnemyStateChanged {
+    pub enemy: Entity,
+    pub from: EnemyState,
+    pub to: EnemyState,
+}
+
+// -----------------------------------------------
+// CONFIGURACIÓN POR TIPO
+// -----------------------------------------------
+
+/// Qué animación suena en cada estado. `clip` es el índice en la lista de
+/// animaciones del tipo de enemigo.
+#[derive(Clone, Debug, Reflect, Deserialize)]
+pub struct StateAnimation {
+    pub state: EnemyState,
+    pub clip: usize,
+    pub speed: f32,
+    pub repeat: bool,
+    pub blend_ms: u64,
+}
+
+/// Cómo ataca el enemigo al salir del windup
+#[derive(Clone, Debug, Reflect, Deserialize)]
+pub enum EnemyAttack {
+    /// Golpe en el sitio. `reach` es el alcance real al terminar el windup:
+    /// alejarse a tiempo lo esquiva.
+    Melee { reach: f32, damage: f32 },
+    /// Embestida en línea recta (ver `charge_attack`)
+    Charge(ChargeSettings),
+    /// Picado desde el aire hacia el jugador (ver `flying_enemy`)
+    Dive(DiveSettings),
+}
+
+impl EnemyAttack {
+    /// Daño y empuje si el ataque hace daño por contacto
+    pub fn contact_damage(&self) -> Option<(f32, f32)> {
+        match self {
+            EnemyAttack::Melee { .. } => None,
+            EnemyAttack::Charge(charge) => Some((charge.damage, charge.knockback)),
+            EnemyAttack::Dive(dive) => Some((dive.damage, dive.knockback)),
+        }
+    }
+
+    /// Si no se le puede tumbar mientras ejecuta el ataque
+    pub fn hyper_armor(&self) -> bool {
+        match self {
+            EnemyAttack::Melee { .. } => false,
+            EnemyAttack::Charge(charge) => charge.hyper_armor,
+            EnemyAttack::Dive(dive) => dive.hyper_armor,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Reflect, Deserialize)]
+pub struct ChargeSettings {
+    pub max_speed: f32,
+    pub acceleration: f32,
+    pub damage: f32,
+    /// Velocidad con la que sale despedido el jugador
+    pub knockback: f32,
+    /// Segundos desde el inicio del clip de golpe hasta el frame del impacto
+    pub bash_lead: f32,
+    pub bash_clip: usize,
+    pub wall_stun_time: f32,
+    /// No se le puede tumbar mientras embiste
+    #[serde(default)]
+    pub hyper_armor: bool,
+}
+
+#[derive(Clone, Debug, Reflect, Deserialize)]
+pub struct DiveSettings {
+    pub speed: f32,
+    pub damage: f32,
+    pub knockback: f32,
+    /// Aturdido si se estampa contra el suelo
+    pub ground_stun_time: f32,
+    /// No se le puede tumbar mientras pica
+    #[serde(default)]
+    pub hyper_armor: bool,
+}
+
+#[derive(Component, Clone, Debug, Reflect, Deserialize)]
+#[reflect(Component)]
+pub struct EnemyBehaviour {
+    pub perception: PerceptionSettings,
+    /// Si el jugador se aleja más que esto deja de perseguirlo
+    pub lose_range: f32,
+    /// Distancia a la que empieza el windup
+    pub attack_range: f32,
+    /// Sin token de ataque, se queda rondando a esta distancia
+    pub circle_range: f32,
+    pub attack: EnemyAttack,
+    pub attack_cooldown: f32,
+    pub walk_speed: f32,
+    pub chase_speed: f32,
+    pub idle_time: f32,
+    /// 0 = no patrulla
+    pub patrol_radius: f32,
+    pub alert_time: f32,
+    /// Tiempo buscando en la última posición conocida antes de rendirse
+    pub search_time: f32,
+    pub windup_time: f32,
+    /// En una embestida, lo máximo que dura antes de rendirse
+    pub attack_time: f32,
+    pub recover_time: f32,
+    pub stun_time: f32,
+    pub animations: Vec<StateAnimation>,
+}
+
+impl EnemyBehaviour {
+    pub fn animation(&self, state: EnemyState) -> Option<&StateAnimation> {
+        self.animations.iter().find(|a| a.state == state)
+    }
+}
+
+// -----------------------------------------------
+// TRANSICIONES
+// -----------------------------------------------
+
+/// Lo que el enemigo sabe este frame
+#[derive(Clone, Copy, Debug)]
+pub struct BrainInputs {
+    pub distance_to_player: f32,
+    pub player_alive: bool,
+    pub sees_player: bool,
+    pub heard_noise: bool,
+    /// Tiene una última posición conocida en memoria
+    pub knows_where: bool,
+    pub reached_last_known: bool,
+    pub cooldown_ready: bool,
+    /// El coordinador de combate le deja atacar
+    pub has_attack_token: bool,
+    pub reached_patrol_target: bool,
+    pub hit: bool,
+    pub dead: bool,
+    /// Cómo acabó la embestida en curso, si ya acabó
+    pub charge_outcome: Option<ChargeOutcome>,
+    pub stun_time: f32,
+}
+
+pub fn next_state(
+    state: EnemyState,
+    time_in_state: f32,
+    inputs: &BrainInputs,
+    behaviour: &EnemyBehaviour,
+) -> Option<EnemyState> {
+    use EnemyState::*;
+
+    if state == Dead {
+        return None;
+    }
+    if inputs.dead {
+        return Some(Dead);
+    }
+    // Un golpe interrumpe lo que sea, también otro stun (reinicia el tiempo)
+    if inputs.hit {
+        return Some(Stunned);
+    }
+
+    let sees_player = inputs.player_alive && inputs.sees_player;
+    let noticed = sees_player || (inputs.player_alive && inputs.heard_noise);
+    // Sin verlo: a la última posición conocida o a buscar
+    let pursue = if sees_player { Chase } else { Search };
+
+    match state {
+        Attack if inputs.charge_outcome == Some(ChargeOutcome::HitWall) => Some(Stunned),
+        Attack if inputs.charge_outcome == Some(ChargeOutcome::HitPlayer) => Some(Recover),
+        Idle | Patrol if noticed => Some(Alert),
+        Idle if time_in_state >= behaviour.idle_time && behaviour.patrol_radius > 0.0 => Some(Patrol),
+        Patrol if inputs.reached_patrol_target => Some(Idle),
+        Alert if time_in_state >= behaviour.alert_time => Some(if inputs.knows_where || sees_player { pursue } else { Idle }),
+        Chase | Search if !inputs.player_alive => Some(Idle),
+        Chase if inputs.distance_to_player > behaviour.lose_range => Some(Search),
+        Chase if sees_player && inputs.distance_to_player <= behaviour.attack_range && inputs.cooldown_ready && inputs.has_attack_token => Some(Windup),
+        Chase if !sees_player && (!inputs.knows_where || inputs.reached_last_known) => Some(Search),
+        Search if sees_player => Some(Chase),
+        Search if inputs.heard_noise => Some(Alert),
+        Search if time_in_state >= behaviour.search_time => Some(Idle),
+        Windup if time_in_state >= behaviour.windup_time => Some(Attack),
+        Attack if time_in_state >= behaviour.attack_time => Some(Recover),
+        Recover if time_in_state >= behaviour.recover_time => Some(pursue),
+        Stunned if time_in_state >= inputs.stun_time => Some(if inputs.knows_where { Chase } else { Search }),
+        _ => None,
+    }
+}
+
+// -----------------------------------------------
+// SISTEMAS
+// -----------------------------------------------
+
+const PATROL_ARRIVE_DISTANCE: f32 = 0.6;
+const LAST_KNOWN_ARRIVE_DISTANCE: f32 = 1.5;
+/// Velocidad a la que gira mirando alrededor mientras busca (rad/s)
+const SEARCH_TURN_SPEED: f32 = 1.2;
+
+pub(super) fn update_enemy_brains(
+    time: Res<Time>,
+    mut impacts: MessageReader<MeleeImpact>,
+    mut enemies: Query<(
+        Entity,
+        &Transform,
+        &mut Enemy,
+        &mut EnemyBrain,
+        &EnemyBehaviour,
+        &mut Perception,
+        Option<&TimeDilation>,
+        Option<&Charging>,
+        Option<&AiLod>,
+        Has<AttackToken>,
+    )>,
+    player: Single<(&Transform, &Player)>,
+    spatial_query: SpatialQuery,
+    mut changed_writer: MessageWriter<EnemyStateChanged>,
+) {
+    let (player_transform, player) = *player;
+    let hits: Vec<Entity> = impacts.read().filter(|i| !i.lethal && i.staggered).map(|i| i.enemy).collect();
+    let mut rng = rand::rng();
+
+    for (entity, transform, mut enemy, mut brain, behaviour, mut perception, dilation, charging, lod, has_attack_token) in enemies.iter_mut() {
+        let Some(dt) = ai_lod::think_dt(lod, time.delta_secs()) else { continue; };
+        let dt = dt * dilation.map_or(1.0, |d| d.scale);
+        brain.time_in_state += dt;
+        enemy.attack_cooldown -= dt;
+
+        let reached_patrol_target = brain
+            .patrol_target
+            .is_none_or(|t| t.xz().distance(transform.translation.xz()) <= PATROL_ARRIVE_DISTANCE);
+
+        let inputs = BrainInputs {
+            distance_to_player: transform.translation.distance(player_transform.translation),
+            player_alive: player.is_alive(),
+            sees_player: perception.sees_player,
+            heard_noise: perception.heard,
+            knows_where: perception.last_known.is_some(),
+            reached_last_known: perception
+                .last_known
+                .is_some_and(|p| p.xz().distance(transform.translation.xz()) <= LAST_KNOWN_ARRIVE_DISTANCE),
+            cooldown_ready: enemy.attack_cooldown <= 0.0,
+            has_attack_token,
+            reached_patrol_target,
+            hit: hits.contains(&entity),
+            dead: enemy.health == 0,
+            charge_outcome: charging.and_then(|c| c.outcome),
+            stun_time: brain.stun_time,
+        };
+        perception.heard = false;
+
+        let Some(to) = next_state(brain.state, brain.time_in_state, &inputs, behaviour) else {
+            continue;
+        };
+
+        if to == EnemyState::Patrol {
+            // Punto al azar alrededor de casa, solo si hay suelo debajo
+            let offset = Vec2::new(rng.random_range(-1.0..1.0_f32), rng.random_range(-1.0..1.0_f32))
+                .normalize_or_zero()
+                * behaviour.patrol_radius;
+            let candidate = brain.home + Vec3::new(offset.x, 0.0, offset.y);
+            let has_ground = spatial_query
+                .cast_ray(candidate + Vec3::Y, Dir3::NEG_Y, 3.0, true, &SpatialQueryFilter::from_excluded_entities([entity]))
+                .is_some();
+            if !has_ground {
+                brain.time_in_state = 0.0;
+                continue;
+            }
+            brain.patrol_target = Some(candidate);
+        }
+
+        if to == EnemyState::Stunned {
+            brain.stun_time = match (&behaviour.attack, inputs.charge_outcome) {
+                (EnemyAttack::Charge(charge), Some(ChargeOutcome::HitWall)) => charge.wall_stun_time,
+                (EnemyAttack::Dive(dive), Some(ChargeOutcome::HitWall)) => dive.ground_stun_time,
+                _ => behaviour.stun_time,
+            };
+        }
+
+        let from = brain.state;
+        brain.state = to;
+        brain.time_in_state = 0.0;
+        changed_writer.write(EnemyStateChanged { enemy: entity, from, to });
+    }
+}
+
+/// Hooks de entrada y salida de cada estado
+pub(super) fn enemy_state_hooks(
+    mut commands: Commands,
+    mut changes: MessageReader<EnemyStateChanged>,
+    mut enemies: Query<(&Transform, &mut Enemy, &EnemyBehaviour, &Perception)>,
+    mut noise_writer: MessageWriter<Noise>,
+    links: Query<&EnemyAnimationLink>,
+    mut anim_players: Query<(&mut AnimationPlayer, &mut AnimationTransitions, &mut EnemyAnimations)>,
+    telegraphs: Query<(Entity, &WindupTelegraph)>,
+    mut player: Single<(&Transform, &mut Player)>,
+    telegraph_assets: Res<WindupTelegraphAssets>,
+) {
+    for change in changes.read() {
+        let Ok((transform, mut enemy, behaviour, perception)) = enemies.get_mut(change.enemy) else { continue; };
+
+        // --- salida ---
+        match change.from {
+            EnemyState::Windup => {
+                for (telegraph_entity, telegraph) in telegraphs.iter() {
+                    if telegraph.enemy == change.enemy {
+                        commands.entity(telegraph_entity).despawn();
+                    }
+                }
+            }
+            EnemyState::Attack => {
+                commands.entity(change.enemy).remove::<Charging>();
+            }
+            _ => {}
+        }
+
+        // --- entrada ---
+        match change.to {
+            // Si lo ha visto él mismo, avisa a los de alrededor
+            EnemyState::Alert if perception.sees_player => {
+                noise_writer.write(perception::ally_alert(
+                    change.enemy,
+                    transform.translation,
+                    player.0.translation,
+                    behaviour.perception.ally_alert_radius,
+                ));
+            }
+            EnemyState::Windup => {
+                commands.entity(change.enemy).with_child((
+                    Name::new("WindupTelegraph"),
+                    WindupTelegraph { enemy: change.enemy, duration: behaviour.windup_time },
+                    Mesh3d(telegraph_assets.mesh.clone()),
+                    MeshMaterial3d(telegraph_assets.material.clone()),
+                    Transform::from_xyz(0.0, 3.0, 0.0).with_scale(Vec3::splat(0.05)),
+                ));
+            }
+            EnemyState::Attack => {
+                enemy.attack_cooldown = behaviour.attack_cooldown;
+                match &behaviour.attack {
+                    EnemyAttack::Melee { reach, damage } => {
+                        if transform.translation.distance(player.0.translation) <= *reach {
+                            player.1.health = (player.1.health - damage).max(0.0);
+                            info!("Enemy atacó al jugador! Player health: {:.2}", player.1.health);
+                        } else {
+                            info!("Ataque esquivado");
+                        }
+                    }
+                    EnemyAttack::Charge(_) => {
+                        // La dirección se fija aquí: si el jugador se aparta, pasa de largo
+                        let dir = (player.0.translation - transform.translation).with_y(0.0).normalize_or_zero();
+                        commands.entity(change.enemy).insert(Charging::new(dir));
+                    }
+                    EnemyAttack::Dive(_) => {
+                        let target = player.0.translation + Vec3::Y * perception::PLAYER_CHEST;
+                        let dir = (target - transform.translation).normalize_or_zero();
+                        commands.entity(change.enemy).insert(Charging::new(dir));
+                    }
+                }
+            }
+            _ => {}
+        }
+
+        // Animación del nuevo estado
+        let Some(animation) = behaviour.animation(change.to) else { continue; };
+        let Some((mut anim_player, mut transitions, mut anims)) = links
+            .get(change.enemy)
+            .ok()
+            .and_then(|link| anim_players.get_mut(link.player()).ok())
+        else {
+            continue;
+        };
+        let Some(&node) = anims.nodes.get(animation.clip) else { continue; };
+        anims.speed = animation.speed;
+
+        // El ataque sigue la animación del windup en vez de empezarla otra vez
+        if change.from == EnemyState::Windup && change.to == EnemyState::Attack && anim_player.is_playing_animation(node) {
+            for (_, active) in anim_player.playing_animations_mut() {
+                active.set_speed(animation.speed);
+            }
+            continue;
+        }
+
+        let active = transitions
+            .play(&mut anim_player, node, Duration::from_millis(animation.blend_ms))
+            .set_speed(animation.speed);
+        if animation.repeat {
+            active.repeat();
+        }
+    }
+}
+
+pub(super) fn enemy_locomotion(
+    time: Res<Time>,
+    navmesh_done: Res<NavmeshDone>,
+    mut enemies: Query<(
+        Entity,
+        &Transform,
+        &mut LinearVelocity,
+        &mut Rotation,
+        &EnemyBrain,
+        &EnemyBehaviour,
+        &Perception,
+        Option<&TimeDilation>,
+        Option<&AgentDesiredVelocity3d>,
+        Option<&AiLod>,
+        Has<AttackToken>,
+    ), (Without<Charging>, Without<Flying>)>,
+    player: Single<&Transform, With<Player>>,
+    camera: Single<&GlobalTransform, With<Camera3d>>,
+) {
+    for (entity, transform, mut linear_velocity, mut rotation, brain, behaviour, perception, dilation, desired, lod, has_token) in enemies.iter_mut() {
+        if lod.is_some_and(|lod| lod.level == LodLevel::Asleep) {
+            continue;
+        }
+        let time_scale = dilation.map_or(1.0, |d| d.scale);
+        let to_player = (player.translation - transform.translation).with_y(0.0).normalize_or_zero();
+        let distance_to_player = transform.translation.xz().distance(player.translation.xz());
+        // Adónde ir si no lo ve: lo último que sabe
+        let to_last_known = perception
+            .last_known
+            .map(|p| (p - transform.translation).with_y(0.0))
+            .filter(|d| d.length() > LAST_KNOWN_ARRIVE_DISTANCE)
+            .map_or(Vec3::ZERO, |d| d.normalize_or_zero());
+
+        let (velocity, facing) = match brain.state {
+            EnemyState::Patrol => {
+                let target = brain.patrol_target.unwrap_or(transform.translation);
+                let dir = (target - transform.translation).with_y(0.0).normalize_or_zero();
+                (dir * behaviour.walk_speed, dir)
+            }
+            // Sin token, al llegar a media distancia espera su turno
+            EnemyState::Chase if !has_token && perception.sees_player && distance_to_player <= behaviour.circle_range * 1.2 => {
+                let velocity = waiting_velocity(
+                    WaitingTactic::for_enemy(entity),
+                    transform.translation,
+                    player.translation,
+                    camera.forward().as_vec3(),
+                    behaviour,
+                );
+                (velocity, to_player)
+            }
+            // Con navmesh se sigue el camino de landmass; mientras se genera,
+            // línea recta hacia el jugador
+            EnemyState::Chase => {
+                let velocity = match desired {
+                    Some(desired) if navmesh_done.0 => desired.velocity().with_y(0.0),
+                    _ if perception.sees_player => to_player * behaviour.chase_speed,
+                    _ => to_last_known * behaviour.chase_speed,
+                };
+                (velocity, velocity.normalize_or_zero())
+            }
+            // Va andando a la última posición conocida y, al llegar, mira alrededor
+            EnemyState::Search => {
+                if to_last_known != Vec3::ZERO {
+                    let velocity = match desired {
+                        Some(desired) if navmesh_done.0 => desired.velocity().with_y(0.0).clamp_length_max(behaviour.walk_speed),
+                        _ => to_last_known * behaviour.walk_speed,
+                    };
+                    (velocity, velocity.normalize_or_zero())
+                } else {
+                    let turn = Quat::from_rotation_y(SEARCH_TURN_SPEED * time.delta_secs() * time_scale);
+                    (Vec3::ZERO, turn * transform.forward().as_vec3())
+                }
+            }
+            // Quieto pero mirando al jugador
+            EnemyState::Alert | EnemyState::Windup => (Vec3::ZERO, to_player),
+            _ => (Vec3::ZERO, Vec3::ZERO),
+        };
+
+        linear_velocity.x = velocity.x * time_scale;
+        linear_velocity.z = velocity.z * time_scale;
+        if facing.length_squared() > 0.001 {
+            *rotation = Quat::from_rotation_y((-facing.x).atan2(-facing.z)).into();
+        }
+    }
+}
+
+/// Movimiento de un enemigo que persigue sin token de ataque
+pub(super) fn waiting_velocity(
+    tactic: WaitingTactic,
+    position: Vec3,
+    player_position: Vec3,
+    player_forward: Vec3,
+    behaviour: &EnemyBehaviour,
+) -> Vec3 {
+    let offset = (position - player_position).with_y(0.0);
+    let distance = offset.length();
+    let away = offset.normalize_or_zero();
+    // Corrige hacia el anillo de `circle_range`
+    let keep_ring = away * ((behaviour.circle_range - distance) / behaviour.circle_range.max(0.01)).clamp(-1.0, 1.0);
+
+    match tactic {
+        WaitingTactic::Circle => {
+            let around = Vec3::Y.cross(away);
+            (around + keep_ring * 2.0).normalize_or_zero() * behaviour.walk_speed
+        }
+        WaitingTactic::Flank => {
+            let behind = player_position - player_forward.with_y(0.0).normalize_or_zero() * behaviour.circle_range;
+            let to_spot = (behind - position).with_y(0.0);
+            if to_spot.length() <= LAST_KNOWN_ARRIVE_DISTANCE {
+                Vec3::ZERO
+            } else {
+                // Rodea por fuera del anillo en vez de cruzar por delante
+                (to_spot.normalize_or_zero() + keep_ring * 2.0).normalize_or_zero() * behaviour.chase_speed
+            }
+        }
+        WaitingTactic::Wait => keep_ring * behaviour.walk_speed,
+    }
+}
+
+// -----------------------------------------------
+// TELEGRAPH DEL WINDUP
+// -----------------------------------------------
+
+/// Esfera roja que crece sobre la cabeza mientras el enemigo carga el golpe
+#[derive(Component)]
+pub struct WindupTelegraph {
+    pub enemy: Entity,
+    pub duration: f32,
+}
+
+/// Malla y material compartidos por todos los telegraphs
+#[derive(Resource)]
+pub struct WindupTelegraphAssets {
+    mesh: Handle<Mesh>,
+    material: Handle<StandardMaterial>,
+}
+
+impl FromWorld for WindupTelegraphAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mesh = world.resource_mut::<Assets<Mesh>>().add(Sphere { radius: 1.0 });
+        let material = world.resource_mut::<Assets<StandardMaterial>>().add(StandardMaterial {
+            base_color: Color::srgba(1.0, 0.1, 0.05, 0.6),
+            emissive: LinearRgba::new(6.0, 0.4, 0.1, 1.0),
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
+            ..default()
+        });
+        Self { mesh, material }
+    }
+}
+
+pub(super) fn windup_telegraph_tick(
+    mut commands: Commands,
+    brains: Query<&EnemyBrain>,
+    mut telegraphs: Query<(Entity, &WindupTelegraph, &mut Transform)>,
+) {
+    for (entity, telegraph, mut transform) in telegraphs.iter_mut() {
+        // Su enemigo ya no está (p. ej. se cayó al vacío)
+        let Ok(brain) = brains.get(telegraph.enemy) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        let k = (brain.time_in_state / telegraph.duration.max(0.01)).clamp(0.0, 1.0);
+        // Crece y late más rápido justo antes del golpe
+        let pulse = 1.0 + (brain.time_in_state * (10.0 + 30.0 * k)).sin() * 0.15 * k;
+        transform.scale = Vec3::splat((0.05 + 0.3 * k) * pulse);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use EnemyState::*;
+
+    fn behaviour() -> EnemyBehaviour {
+        EnemyBehaviour {
+            perception: PerceptionSettings::default(),
+            lose_range: 25.0,
+            attack_range: 2.2,
+            circle_range: 5.0,
+            attack: EnemyAttack::Melee { reach: 2.5, damage: 10.0 },
+            attack_cooldown: 1.0,
+            walk_speed: 2.0,
+            chase_speed: 5.0,
+            idle_time: 2.0,
+            patrol_radius: 0.0,
+            alert_time: 0.5,
+            search_time: 4.0,
+            windup_time: 0.6,
+            attack_time: 0.4,
+            recover_time: 0.8,
+            stun_time: 1.0,
+            animations: Vec::new(),
+        }
+    }
+
+    /// Jugador vivo, a 10 m, sin que el enemigo sepa nada
+    fn inputs() -> BrainInputs {
+        BrainInputs {
+            distance_to_player: 10.0,
+            player_alive: true,
+            sees_player: false,
+            heard_noise: false,
+            knows_where: false,
+            reached_last_known: false,
+            cooldown_ready: true,
+            has_attack_token: true,
+            reached_patrol_target: false,
+            hit: false,
+            dead: false,
+            charge_outcome: None,
+            stun_time: 1.0,
+        }
+    }
+
+    #[test]
+    fn idle_chases_after_seeing_the_player() {
+        let behaviour = behaviour();
+        let seen = BrainInputs { sees_player: true, knows_where: true, ..inputs() };
+        assert_eq!(next_state(Idle, 0.0, &seen, &behaviour), Some(Alert));
+        assert_eq!(next_state(Alert, 0.1, &seen, &behaviour), None);
+        assert_eq!(next_state(Alert, behaviour.alert_time, &seen, &behaviour), Some(Chase));
+    }
+
+    #[test]
+    fn idle_stays_idle_without_noticing() {
+        let behaviour = behaviour();
+        assert_eq!(next_state(Idle, 10.0, &inputs(), &behaviour), None);
+    }
+
+    #[test]
+    fn chase_searches_when_the_player_is_lost() {
+        let behaviour = behaviour();
+        let out_of_range = BrainInputs { sees_player: true, knows_where: true, distance_to_player: 30.0, ..inputs() };
+        assert_eq!(next_state(Chase, 1.0, &out_of_range, &behaviour), Some(Search));
+        let out_of_sight = BrainInputs { knows_where: true, reached_last_known: true, ..inputs() };
+        assert_eq!(next_state(Chase, 1.0, &out_of_sight, &behaviour), Some(Search));
+    }
+
+    #[test]
+    fn windup_attacks_then_recovers() {
+        let behaviour = behaviour();
+        let close = BrainInputs { sees_player: true, knows_where: true, distance_to_player: 2.0, ..inputs() };
+        assert_eq!(next_state(Chase, 0.0, &close, &behaviour), Some(Windup));
+        assert_eq!(next_state(Windup, 0.1, &close, &behaviour), None);
+        assert_eq!(next_state(Windup, behaviour.windup_time, &close, &behaviour), Some(Attack));
+        assert_eq!(next_state(Attack, behaviour.attack_time, &close, &behaviour), Some(Recover));
+        assert_eq!(next_state(Recover, behaviour.recover_time, &close, &behaviour), Some(Chase));
+    }
+
+    #[test]
+    fn windup_without_token_keeps_chasing() {
+        let behaviour = behaviour();
+        let close = BrainInputs {
+            sees_player: true,
+            knows_where: true,
+            distance_to_player: 2.0,
+            has_attack_token: false,
+            ..inputs()
+        };
+        assert_eq!(next_state(Chase, 0.0, &close, &behaviour), None);
+    }
+
+    #[test]
+    fn stun_wears_off() {
+        let behaviour = behaviour();
+        let hit = BrainInputs { hit: true, ..inputs() };
+        assert_eq!(next_state(Windup, 0.2, &hit, &behaviour), Some(Stunned));
+        assert_eq!(next_state(Stunned, 0.5, &inputs(), &behaviour), None);
+        assert_eq!(next_state(Stunned, 1.0, &inputs(), &behaviour), Some(Search));
+        let remembers = BrainInputs { knows_where: true, ..inputs() };
+        assert_eq!(next_state(Stunned, 1.0, &remembers, &behaviour), Some(Chase));
+    }
+
+    #[test]
+    fn dead_is_final() {
+        let behaviour = behaviour();
+        let dead = BrainInputs { dead: true, ..inputs() };
+        assert_eq!(next_state(Chase, 0.0, &dead, &behaviour), Some(Dead));
+        assert_eq!(next_state(Dead, 10.0, &BrainInputs { hit: true, ..inputs() }, &behaviour), None);
+    }
+}
@@ -0,0 +1,293 @@
+//! Tipos de enemigo definidos en archivos `.enemy.ron`
+//!
+//! Cada archivo de `assets/enemies/` describe un tipo: vida, collider,
+//! modelo y animaciones, parámetros de la IA (`EnemyBehaviour`), loot,
+//! sonidos, cómo muere, si anda o vuela, sus reglas de oleada y, si es un
+//! jefe, sus fases. Se cargan
+//! con `asset_tracking` como el resto de assets del nivel, y `spawn_enemy`
+//! los busca por el nombre que viene del `EnemySpawn.r#type` de Blender.
+
+use std::fmt;
+
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    prelude::*,
+};
+use bevy_seedling::sample::AudioSample;
+use serde::Deserialize;
+
+use crate::asset_tracking::LoadResource;
+use crate::screens::gameplay::{
+    boss::BossSettings,
+    enemy_ai::EnemyBehaviour, enemy_death::DeathSettings, flying_enemy::FlightSettings, loot::LootTableId,
+    poise::PoiseSettings,
+};
+
+/// Tipo que se usa cuando el empty de Blender no dice ninguno
+pub const DEFAULT_ARCHETYPE: &str = "hammerhead";
+
+/// Archivos de tipos de enemigo. En web no se puede listar una carpeta, así
+/// que hay que añadir aquí cada archivo nuevo.
+const ARCHETYPE_FILES: &[&str] = &[
+    "enemies/hammerhead.enemy.ron",
+    "enemies/skyhammer.enemy.ron",
+    "enemies/cloud_king.enemy.ron",
+];
+
+pub(super) fn plugin(app: &mut App) {
+    app.init_asset::<EnemyArchetype>();
+    app.init_asset_loader::<EnemyArchetypeLoader>();
+    app.load_resource::<EnemyArchetypes>();
+}
+
+// -----------------------------------------------
+// DEFINICIÓN (lo que hay en el archivo)
+// -----------------------------------------------
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct EnemyArchetypeDef {
+    /// Nombre con el que se busca desde Blender (`EnemySpawn.r#type`)
+    pub name: String,
+    pub health: u32,
+    pub collider: ColliderDef,
+    /// glb del que sale la escena 0
+    pub model: String,
+    /// Clips en el orden que usan los `StateAnimation` del `behaviour`
+    pub animations: Vec<ClipDef>,
+    pub behaviour: EnemyBehaviour,
+    pub loot: Option<LootTableId>,
+    #[serde(default)]
+    pub sounds: SoundsDef,
+    #[serde(default)]
+    pub death: DeathSettings,
+    /// Sin `poise` cualquier golpe lo tumba
+    #[serde(default)]
+    pub poise: PoiseSettings,
+    #[serde(default)]
+    pub movement: EnemyMovement,
+    #[serde(default)]
+    pub spawn: SpawnRules,
+    /// Escala del modelo y del collider
+    #[serde(default = "default_scale")]
+    pub scale: f32,
+    /// Solo los jefes (ver `boss`)
+    #[serde(default)]
+    pub boss: Option<BossSettings>,
+}
+
+fn default_scale() -> f32 {
+    1.0
+}
+
+#[derive(Deserialize, Clone, Debug, Default)]
+pub enum EnemyMovement {
+    /// Anda por el navmesh con gravedad
+    #[default]
+    Ground,
+    Flying(FlightSettings),
+}
+
+/// Cuándo y cómo entra este tipo en las oleadas
+#[derive(Deserialize, Clone, Debug)]
+pub struct SpawnRules {
+    /// No aparece antes de esta ola
+    pub min_wave: i32,
+    /// Como mucho tantos vivos a la vez
+    pub max_alive: Option<usize>,
+    /// Se spawnea esto por encima del empty
+    pub spawn_height: f32,
+}
+
+impl Default for SpawnRules {
+    fn default() -> Self {
+        Self { min_wave: 0, max_alive: None, spawn_height: 0.0 }
+    }
+}
+
+#[derive(Deserialize, Clone, Copy, Debug)]
+pub struct ColliderDef {
+    pub radius: f32,
+    pub length: f32,
+    /// Altura del centro de la cápsula sobre los pies
+    pub height: f32,
+}
+
+#[derive(Deserialize, Clone, Debug)]
+pub struct ClipDef {
+    pub file: String,
+    pub index: usize,
+}
+
+/// Si no se pone, suenan los de `LevelAssets`
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct SoundsDef {
+    pub hit: Option<String>,
+    pub death: Option<String>,
+}
+
+// -----------------------------------------------
+// ASSET
+// -----------------------------------------------
+
+#[derive(Asset, TypePath, Clone, Debug)]
+pub struct EnemyArchetype {
+    pub def: EnemyArchetypeDef,
+    #[dependency]
+    pub scene: Handle<Scene>,
+    #[dependency]
+    pub animations: Vec<Handle<AnimationClip>>,
+    /// Grafo con todos los clips, compartido por los enemigos de este tipo
+    pub graph: Handle<AnimationGraph>,
+    /// Nodo del grafo de cada clip, en el orden de `animations`
+    pub animation_nodes: Vec<AnimationNodeIndex>,
+    #[dependency]
+    pub hit_sound: Option<Handle<AudioSample>>,
+    #[dependency]
+    pub death_sound: Option<Handle<AudioSample>>,
+}
+
+/// Qué tipo es cada enemigo
+#[derive(Component, Clone, Debug)]
+pub struct EnemyArchetypeHandle(pub Handle<EnemyArchetype>);
+
+#[derive(Default, TypePath)]
+struct EnemyArchetypeLoader;
+
+#[derive(Debug)]
+pub enum EnemyArchetypeError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+}
+
+impl fmt::Display for EnemyArchetypeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EnemyArchetypeError::Io(err) => write!(f, "no se pudo leer el tipo de enemigo: {err}"),
+            EnemyArchetypeError::Ron(err) => write!(f, "tipo de enemigo mal escrito: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for EnemyArchetypeError {}
+
+impl From<std::io::Error> for EnemyArchetypeError {
+    fn from(err: std::io::Error) -> Self {
+        EnemyArchetypeError::Io(err)
+    }
+}
+
+impl From<ron::error::SpannedError> for EnemyArchetypeError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        EnemyArchetypeError::Ron(err)
+    }
+}
+
+impl AssetLoader for EnemyArchetypeLoader {
+    type Asset = EnemyArchetype;
+    type Settings = ();
+    type Error = EnemyArchetypeError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let def: EnemyArchetypeDef = ron::de::from_bytes(&bytes)?;
+
+        let scene = load_context.load(GltfAssetLabel::Scene(0).from_asset(def.model.clone()));
+        let animations: Vec<Handle<AnimationClip>> = def
+            .animations
+            .iter()
+            .map(|clip| load_context.load(GltfAssetLabel::Animation(clip.index).from_asset(clip.file.clone())))
+            .collect();
+        let (graph, animation_nodes) = AnimationGraph::from_clips(animations.clone());
+        let graph = load_context.add_labeled_asset("graph".to_string(), graph);
+        let hit_sound = def.sounds.hit.clone().map(|path| load_context.load(path));
+        let death_sound = def.sounds.death.clone().map(|path| load_context.load(path));
+
+        Ok(EnemyArchetype {
+            def,
+            scene,
+            animations,
+            graph,
+            animation_nodes,
+            hit_sound,
+            death_sound,
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["enemy.ron"]
+    }
+}
+
+// -----------------------------------------------
+// REGISTRO
+// -----------------------------------------------
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct EnemyArchetypes {
+    #[dependency]
+    handles: Vec<Handle<EnemyArchetype>>,
+}
+
+impl FromWorld for EnemyArchetypes {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self {
+            handles: ARCHETYPE_FILES.iter().map(|path| assets.load(*path)).collect(),
+        }
+    }
+}
+
+/// Se pidió un tipo de enemigo que no existe
+#[derive(Debug)]
+pub struct UnknownArchetype {
+    pub name: String,
+    pub known: Vec<String>,
+}
+
+impl fmt::Display for UnknownArchetype {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Tipo de enemigo desconocido '{}' (hay: {}). ¿Falta el archivo en ARCHETYPE_FILES?",
+            self.name,
+            self.known.join(", "),
+        )
+    }
+}
+
+impl std::error::Error for UnknownArchetype {}
+
+impl EnemyArchetypes {
+    /// Busca un tipo por nombre. Un nombre vacío es el tipo por defecto.
+    pub fn get<'a>(
+        &self,
+        name: &str,
+        archetypes: &'a Assets<EnemyArchetype>,
+    ) -> Result<(Handle<EnemyArchetype>, &'a EnemyArchetype), UnknownArchetype> {
+        let name = if name.trim().is_empty() { DEFAULT_ARCHETYPE } else { name.trim() };
+        self.handles
+            .iter()
+            .find_map(|handle| {
+                archetypes
+                    .get(handle)
+                    .filter(|archetype| archetype.def.name.eq_ignore_ascii_case(name))
+                    .map(|archetype| (handle.clone(), archetype))
+            })
+            .ok_or_else(|| UnknownArchetype {
+                name: name.to_string(),
+                known: self
+                    .handles
+                    .iter()
+                    .filter_map(|handle| archetypes.get(handle))
+                    .map(|archetype| archetype.def.name.clone())
+                    .collect(),
+            })
+    }
+}
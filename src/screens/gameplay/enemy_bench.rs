@@ -0,0 +1,183 @@
+//! Benchmark de la IA de enemigos (solo dev)
+//!
+//! `cargo run -- --bench-enemies [n]` arranca una app sin ventana ni render
+//! con un suelo plano, unos pilares para tapar la vista, un jugador dando
+//! vueltas y `n` enemigos (300 por defecto) del tipo `hammerhead`. Corre los
+//! mismos sistemas de IA que el juego (LOD, percepción, coordinador,
+//! cerebro, locomoción, separación, bordes) a 60 fps simulados y saca por
+//! consola cuánto tarda cada tick.
+
+use std::time::{Duration, Instant};
+
+use avian3d::prelude::*;
+use bevy::{prelude::*, time::TimeUpdateStrategy};
+
+use crate::screens::gameplay::{
+    NavmeshDone,
+    ai_lod::{self, AiLod, LodLevel},
+    charge_attack,
+    combat_coordinator::{self, CombatCoordinator},
+    crowd,
+    enemy::{Enemy, Grounded, LastGroundedPosition},
+    enemy_ai::{self, EnemyBrain, EnemyStateChanged},
+    enemy_archetype::EnemyArchetypeDef,
+    hitstop::MeleeImpact,
+    ledge,
+    perception::{self, Noise, Perception},
+    player::Player,
+};
+
+const DEFAULT_ENEMIES: usize = 300;
+const ARCHETYPE_FILE: &str = "assets/enemies/hammerhead.enemy.ron";
+const ARENA_SIZE: f32 = 200.0;
+const WARMUP_FRAMES: usize = 60;
+const MEASURED_FRAMES: usize = 600;
+/// Radio de la vuelta que da el jugador
+const PLAYER_ORBIT: f32 = 40.0;
+
+/// Lee `--bench-enemies [n]` de la línea de comandos
+pub(crate) fn requested() -> Option<usize> {
+    let mut args = std::env::args().skip_while(|arg| arg != "--bench-enemies");
+    args.next()?;
+    Some(args.next().and_then(|n| n.parse().ok()).unwrap_or(DEFAULT_ENEMIES))
+}
+
+pub(crate) fn run(enemies: usize) -> AppExit {
+    let def: EnemyArchetypeDef = match std::fs::read_to_string(ARCHETYPE_FILE)
+        .map_err(|err| err.to_string())
+        .and_then(|text| ron::de::from_str(&text).map_err(|err| err.to_string()))
+    {
+        Ok(def) => def,
+        Err(err) => {
+            eprintln!("No se pudo leer {ARCHETYPE_FILE}: {err}");
+            return AppExit::error();
+        }
+    };
+
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, TransformPlugin, AssetPlugin::default(), PhysicsPlugins::default()));
+    app.init_asset::<Mesh>();
+    app.init_asset::<StandardMaterial>();
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_secs_f64(1.0 / 60.0)));
+    app.add_message::<MeleeImpact>();
+    app.add_message::<EnemyStateChanged>();
+    app.add_message::<Noise>();
+    app.init_resource::<CombatCoordinator>();
+    app.init_resource::<enemy_ai::WindupTelegraphAssets>();
+    app.insert_resource(NavmeshDone(false));
+    app.insert_resource(BenchArchetype(def));
+    app.insert_resource(BenchEnemies(enemies));
+    app.add_systems(Startup, spawn_bench_world);
+    app.add_systems(
+        Update,
+        (
+            orbit_player,
+            ai_lod::insert_ai_lod,
+            ai_lod::update_ai_lod,
+            perception::update_sight,
+            perception::update_hearing,
+            combat_coordinator::release_tokens,
+            combat_coordinator::grant_tokens,
+            enemy_ai::update_enemy_brains,
+            enemy_ai::enemy_state_hooks,
+            enemy_ai::enemy_locomotion,
+            crowd::separation_steering,
+            ledge::stop_at_ledges,
+            charge_attack::charge_tick,
+            enemy_ai::windup_telegraph_tick,
+        )
+            .chain(),
+    );
+
+    // La física tarda un par de frames en tener los colliders listos
+    for _ in 0..WARMUP_FRAMES {
+        app.update();
+    }
+    let mut frames: Vec<f64> = (0..MEASURED_FRAMES)
+        .map(|_| {
+            let start = Instant::now();
+            app.update();
+            start.elapsed().as_secs_f64() * 1000.0
+        })
+        .collect();
+    frames.sort_by(f64::total_cmp);
+
+    let mean = frames.iter().sum::<f64>() / frames.len() as f64;
+    let p95 = frames[frames.len() * 95 / 100];
+    let max = frames[frames.len() - 1];
+    let mut lods = app.world_mut().query::<&AiLod>();
+    let mut count = |level: LodLevel| lods.iter(app.world()).filter(|lod| lod.level == level).count();
+    let (full, reduced, asleep) = (count(LodLevel::Full), count(LodLevel::Reduced), count(LodLevel::Asleep));
+
+    println!("Benchmark de IA: {enemies} enemigos, {MEASURED_FRAMES} frames");
+    println!("  tick medio {mean:.3} ms | p95 {p95:.3} ms | máx {max:.3} ms");
+    println!("  LOD al final: {full} completos, {reduced} reducidos, {asleep} dormidos");
+    AppExit::Success
+}
+
+#[derive(Resource)]
+struct BenchArchetype(EnemyArchetypeDef);
+
+#[derive(Resource)]
+struct BenchEnemies(usize);
+
+fn spawn_bench_world(mut commands: Commands, archetype: Res<BenchArchetype>, count: Res<BenchEnemies>) {
+    let def = &archetype.0;
+
+    commands.spawn((
+        Name::new("BenchGround"),
+        RigidBody::Static,
+        Collider::cuboid(ARENA_SIZE, 1.0, ARENA_SIZE),
+        Transform::from_xyz(0.0, -0.5, 0.0),
+    ));
+    // Pilares en rejilla para que la vista no sea siempre directa
+    for x in (-80..=80).step_by(20) {
+        for z in (-80..=80).step_by(20) {
+            commands.spawn((
+                Name::new("BenchPillar"),
+                RigidBody::Static,
+                Collider::cuboid(2.0, 6.0, 2.0),
+                Transform::from_xyz(x as f32 + 10.0, 3.0, z as f32 + 10.0),
+            ));
+        }
+    }
+
+    commands.spawn((Name::new("BenchPlayer"), Player::default(), Transform::from_xyz(PLAYER_ORBIT, 0.0, 0.0)));
+    commands.spawn((Name::new("BenchCamera"), Camera3d::default(), Transform::default()));
+
+    // Repartidos en espiral por la arena, del centro hacia fuera
+    let spacing = ARENA_SIZE * 0.45 / (count.0 as f32).sqrt().max(1.0);
+    for i in 0..count.0 {
+        let angle = i as f32 * 2.4;
+        let radius = (i as f32).sqrt() * spacing;
+        let position = Vec3::new(angle.cos() * radius, 0.0, angle.sin() * radius);
+        commands
+            .spawn((
+                Name::new(format!("BenchEnemy_{i}")),
+                Enemy {
+                    id: i as u32,
+                    health: def.health,
+                    max_health: def.health,
+                    attack_cooldown: 0.0,
+                },
+                EnemyBrain::new(position),
+                def.behaviour.clone(),
+                Perception::default(),
+                LastGroundedPosition(position),
+                Grounded,
+                Transform::from_translation(position),
+                RigidBody::Kinematic,
+                LinearVelocity::default(),
+            ))
+            .with_child((
+                Collider::capsule(def.collider.radius, def.collider.length),
+                Transform::from_xyz(0.0, def.collider.height, 0.0),
+            ));
+    }
+}
+
+/// El jugador da vueltas para que los enemigos cambien de LOD
+fn orbit_player(time: Res<Time>, mut player: Single<&mut Transform, With<Player>>) {
+    let angle = time.elapsed_secs() * 0.3;
+    player.translation = Vec3::new(angle.cos(), 0.0, angle.sin()) * PLAYER_ORBIT;
+}
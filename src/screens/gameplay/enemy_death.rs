@@ -0,0 +1,192 @@
+//! Secuencia de muerte de los enemigos
+//!
+//! `enemy_health_system` ya no despawnea al enemigo que muere en combate: le
+//! pone `Dying`. En ese momento deja de ser una amenaza (se le quitan
+//! `Enemy`, el cerebro, la percepción, el agente de navegación, la barra de
+//! vida y el emisor de partículas), suena su reacción de muerte y el cuerpo:
+//!
+//! - `Ragdoll`: pasa a cuerpo dinámico y sale rodando por el golpe.
+//! - `Dissolve`: se deshace en partículas oscuras mientras se hunde.
+//!
+//! Pasado el `delay` del tipo de enemigo se despawnea del todo.
+
+use std::time::Duration;
+
+use avian3d::prelude::*;
+use bevy::prelude::*;
+use bevy_landmass::prelude::*;
+use rand::RngExt;
+use serde::Deserialize;
+
+use crate::screens::Screen;
+use crate::screens::gameplay::{
+    alarm_clock::TimeDilation,
+    charge_attack::Charging,
+    combat_coordinator::AttackToken,
+    enemy::{Enemy, EnemyAnimationLink, EnemyAnimations, Grounded, Knockback},
+    enemy_ai::{EnemyBehaviour, EnemyBrain, WindupTelegraph},
+    health_bar::HealthBars,
+    particle_system::{EnemyEmitter, ParticleAssets, ParticleBudget, spawn_death_burst},
+    perception::Perception,
+    player::Player,
+};
+
+pub struct EnemyDeathPlugin;
+
+/// Lo que tarda en encogerse un ragdoll antes de desaparecer
+const RAGDOLL_SHRINK_TIME: f32 = 0.6;
+/// Velocidad a la que sale despedido el cuerpo
+const RAGDOLL_LAUNCH_SPEED: f32 = 7.0;
+const DISSOLVE_SINK_SPEED: f32 = 0.6;
+
+impl Plugin for EnemyDeathPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_observer(start_dying);
+        app.add_systems(Update, dying_tick.run_if(in_state(Screen::Gameplay)));
+    }
+}
+
+#[derive(Deserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum DeathStyle {
+    #[default]
+    Ragdoll,
+    Dissolve,
+}
+
+/// Cómo muere un tipo de enemigo (sección `death` del `.enemy.ron`)
+#[derive(Deserialize, Clone, Debug)]
+pub struct DeathSettings {
+    pub style: DeathStyle,
+    /// Segundos hasta que el cuerpo desaparece
+    pub delay: f32,
+    /// Clip de reacción de muerte; sin él se congela la animación
+    pub clip: Option<usize>,
+}
+
+impl Default for DeathSettings {
+    fn default() -> Self {
+        Self {
+            style: DeathStyle::default(),
+            delay: 3.0,
+            clip: None,
+        }
+    }
+}
+
+/// Cuerpo de un enemigo muerto, ya no cuenta como enemigo
+#[derive(Component)]
+pub struct Dying {
+    pub settings: DeathSettings,
+    timer: Timer,
+    start_scale: Vec3,
+}
+
+impl Dying {
+    pub fn new(settings: DeathSettings) -> Self {
+        Self {
+            timer: Timer::from_seconds(settings.delay.max(0.0), TimerMode::Once),
+            settings,
+            start_scale: Vec3::ONE,
+        }
+    }
+}
+
+fn start_dying(
+    add: On<Add, Dying>,
+    mut commands: Commands,
+    mut bodies: Query<(&Transform, &mut Dying)>,
+    links: Query<&EnemyAnimationLink>,
+    mut anim_players: Query<(&mut AnimationPlayer, &mut AnimationTransitions, &mut EnemyAnimations)>,
+    telegraphs: Query<(Entity, &WindupTelegraph)>,
+    player: Single<&Transform, With<Player>>,
+    particle_assets: Res<ParticleAssets>,
+    mut particle_budget: ResMut<ParticleBudget>,
+) {
+    let entity = add.entity;
+    let Ok((transform, mut dying)) = bodies.get_mut(entity) else { return; };
+    dying.start_scale = transform.scale;
+
+    // Deja de ser una amenaza ya mismo
+    commands.entity(entity).remove::<(
+        Enemy,
+        EnemyBrain,
+        EnemyBehaviour,
+        Perception,
+        AttackToken,
+        Charging,
+        Knockback,
+        Grounded,
+        EnemyEmitter,
+        ShapeCaster,
+    )>();
+    commands
+        .entity(entity)
+        .remove::<(Agent3dBundle, AgentTarget3d, Velocity3d)>()
+        .despawn_related::<HealthBars>();
+    for (telegraph_entity, telegraph) in telegraphs.iter() {
+        if telegraph.enemy == entity {
+            commands.entity(telegraph_entity).despawn();
+        }
+    }
+
+    // Reacción de muerte
+    if let Some((mut anim_player, mut transitions, mut anims)) = links
+        .get(entity)
+        .ok()
+        .and_then(|link| anim_players.get_mut(link.player()).ok())
+    {
+        match dying.settings.clip.and_then(|clip| anims.nodes.get(clip).copied()) {
+            Some(node) => {
+                anims.speed = 1.0;
+                transitions.play(&mut anim_player, node, Duration::from_millis(80)).set_speed(1.0);
+            }
+            None => {
+                anim_player.pause_all();
+            }
+        }
+    }
+
+    match dying.settings.style {
+        DeathStyle::Ragdoll => {
+            let mut rng = rand::rng();
+            let away = (transform.translation - player.translation).with_y(0.0).normalize_or_zero();
+            commands.entity(entity).insert((
+                RigidBody::Dynamic,
+                LinearVelocity(away * RAGDOLL_LAUNCH_SPEED + Vec3::Y * 4.0),
+                AngularVelocity(Vec3::new(
+                    rng.random_range(-6.0..6.0),
+                    rng.random_range(-3.0..3.0),
+                    rng.random_range(-6.0..6.0),
+                )),
+            ));
+        }
+        DeathStyle::Dissolve => {
+            commands.entity(entity).insert(LinearVelocity::ZERO);
+            spawn_death_burst(&mut commands, &particle_assets, &mut particle_budget, transform.translation + Vec3::Y);
+        }
+    }
+}
+
+fn dying_tick(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut bodies: Query<(Entity, &mut Transform, &mut Dying, Option<&TimeDilation>)>,
+) {
+    for (entity, mut transform, mut dying, dilation) in bodies.iter_mut() {
+        let dt = time.delta_secs() * dilation.map_or(1.0, |d| d.scale);
+        dying.timer.tick(Duration::from_secs_f32(dt));
+
+        let shrink = match dying.settings.style {
+            DeathStyle::Ragdoll => (dying.timer.remaining_secs() / RAGDOLL_SHRINK_TIME).min(1.0),
+            DeathStyle::Dissolve => {
+                transform.translation.y -= DISSOLVE_SINK_SPEED * dt;
+                1.0 - dying.timer.fraction()
+            }
+        };
+        transform.scale = dying.start_scale * shrink.max(0.01);
+
+        if dying.timer.is_finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
@@ -1,12 +1,69 @@
 use bevy::prelude::*;
 
-struct EnemyKilledEvent;
+use crate::screens::gameplay::{loot::LootTableId, objective::ObjectiveGoal};
+
+/// Un enemigo ha muerto, da igual cómo. Loot, racha de kills y demás
+/// escuchan esto en vez de mirar quién desaparece.
+#[derive(Message, Clone, Copy, Debug)]
+pub struct EnemyKilledEvent {
+    pub enemy: Entity,
+    /// Dónde soltar el loot (si cayó al vacío, el último suelo que pisó)
+    pub position: Vec3,
+    pub cause: DeathCause,
+    /// Quien le dio el último golpe, aunque muriera al caer
+    pub killer: Option<Entity>,
+    pub loot: Option<LootTableId>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum DeathCause {
+    Combat,
+    Fall,
+}
+
 struct CapsuleDestroyedEvent;
-struct ArenaCollapsedEvent;
 struct ComboTriggeredEvent;
-struct LevelWonEvent;
 struct LevelLostEvent;
 
+/// Empieza una oleada de una zona (ver `spawn_enemy_waves`)
+#[derive(Message, Clone, Debug)]
+pub struct WaveStartedEvent {
+    pub zone: String,
+    /// Empiezan en 1
+    pub wave: i32,
+}
+
+/// Se superó una oleada según su `ClearCondition`
+#[derive(Message, Clone, Debug)]
+pub struct WaveClearedEvent {
+    pub zone: String,
+    pub wave: i32,
+}
+
+/// Se superó la última oleada de una zona
+#[derive(Message, Clone, Debug)]
+pub struct ZoneCompletedEvent {
+    pub zone: String,
+}
+
+/// Se acabó el tiempo de una arena y empieza a hundirse el suelo (ver `arena`)
+#[derive(Message, Clone, Debug)]
+pub struct ArenaCollapsedEvent {
+    pub zone: String,
+}
+
+/// Se despejó una arena y se abrieron sus salidas
+#[derive(Message, Clone, Debug)]
+pub struct ArenaClearedEvent {
+    pub zone: String,
+}
+
+/// Se cumplió el objetivo del nivel (ver `objective`)
+#[derive(Message, Clone, Copy, Debug)]
+pub struct LevelWonEvent {
+    pub goal: ObjectiveGoal,
+}
+
 #[derive(Event)]
 pub struct SpawnAlarmClockEvent {
     pub position: Vec3,
@@ -3,7 +3,7 @@ use crate::screens::Screen;
 use avian3d::prelude::*;
 use crate::screens::gameplay::LevelAssets;
 use crate::audio::sound_effect;
-use crate::screens::gameplay::Menu;
+use crate::screens::gameplay::grab::Grabbable;
 
 pub struct FlowerCapsulePlugin;
 
@@ -23,7 +23,6 @@ impl Plugin for FlowerCapsulePlugin {
                 register_shards,     // 2. vincula shards (sin Added, espera cápsulas)
                 force_capsule_base_color,
                 apply_capsule_damage_color,
-                flower_capsule_tracking,
             )
                 .chain()
                 .run_if(in_state(Screen::Gameplay)),
@@ -192,7 +191,7 @@ pub fn damage_capsule(
         for (shard_entity, owner) in shards.iter() {
             if owner.0 == entity {
                 commands.entity(shard_entity)
-                    .insert(RigidBody::Dynamic)
+                    .insert((RigidBody::Dynamic, Grabbable))
                     .insert(LinearVelocity(Vec3::new(
                         rand::random::<f32>() * 4.0 - 2.0,
                         rand::random::<f32>() * 3.0 + 1.0,
@@ -205,15 +204,3 @@ pub fn damage_capsule(
         info!("Cápsula destruida ({}/{})", tracker.broken, tracker.total);
     }
 }
-
-fn flower_capsule_tracking(
-    tracker: Res<CapsuleTracker>,
-    mut next_menu: ResMut<NextState<Menu>>,
-    mut level_won: Local<bool>,
-) {
-    if *level_won { return; }
-    if tracker.all_broken() {
-        *level_won = true;
-        next_menu.set(Menu::Victory);
-    }
-}
@@ -0,0 +1,261 @@
+//! Enemigos voladores
+//!
+//! No usan navmesh ni gravedad: flotan a `hover_height` sobre lo que tengan
+//! debajo. Sin ver al jugador deambulan alrededor de casa como las mariposas
+//! (`ButterflyMovement`: un punto al azar que cambia cada pocos segundos).
+//! Persiguiendo, lo rodean en el aire a `circle_range` y, cuando el
+//! coordinador les da token, se lanzan en picado (`EnemyAttack::Dive`).
+//!
+//! Esquivan el escenario con raycasts. Solo saben mantenerse en el aire si
+//! hay suelo debajo: si un golpe los saca de la nube, caen al vacío.
+
+use avian3d::prelude::*;
+use bevy::prelude::*;
+use rand::RngExt;
+use serde::Deserialize;
+
+use crate::screens::Screen;
+use crate::screens::gameplay::{
+    ai_lod::{AiLod, LodLevel},
+    alarm_clock::TimeDilation,
+    charge_attack::{self, ChargeOutcome, Charging},
+    combat_coordinator::{AttackToken, WaitingTactic},
+    enemy::{Enemy, Knockback, LastGroundedPosition},
+    enemy_ai::{self, EnemyAttack, EnemyBehaviour, EnemyBrain, EnemyState},
+    perception::Perception,
+    player::Player,
+};
+
+pub struct FlyingEnemyPlugin;
+
+const GRAVITY: f32 = -9.81;
+/// Cómo de rápido corrige la altura
+const ALTITUDE_GAIN: f32 = 2.0;
+/// Sin suelo a esta distancia por debajo no puede sostenerse
+const MAX_GROUND_DISTANCE: f32 = 20.0;
+/// Radio del cuerpo para los raycasts de evitación
+const BODY_RADIUS: f32 = 0.5;
+
+impl Plugin for FlyingEnemyPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (flying_locomotion, dive_tick)
+                .chain()
+                .after(enemy_ai::enemy_locomotion)
+                .before(charge_attack::charge_tick)
+                .run_if(in_state(Screen::Gameplay)),
+        );
+    }
+}
+
+/// Cómo vuela un tipo de enemigo (`movement: Flying(...)` en el `.enemy.ron`)
+#[derive(Deserialize, Clone, Debug)]
+pub struct FlightSettings {
+    pub hover_height: f32,
+    /// Radio de deambular alrededor de casa
+    pub wander_radius: f32,
+    pub wander_speed: f32,
+    /// Segundos entre cambios de punto al deambular
+    pub wander_interval: f32,
+    /// Velocidad lateral mientras rodea al jugador
+    pub strafe_speed: f32,
+    /// Distancia de los raycasts para esquivar el escenario
+    pub avoid_distance: f32,
+}
+
+/// Enemigo que vuela en vez de andar
+#[derive(Component)]
+pub struct Flying {
+    pub settings: FlightSettings,
+    /// Como `ButterflyMovement`: hacia dónde va mientras deambula
+    wander_target: Vec3,
+    change_target_timer: Timer,
+}
+
+impl Flying {
+    pub fn new(settings: FlightSettings, home: Vec3) -> Self {
+        Self {
+            change_target_timer: Timer::from_seconds(settings.wander_interval, TimerMode::Repeating),
+            wander_target: home + Vec3::Y * settings.hover_height,
+            settings,
+        }
+    }
+}
+
+fn flying_locomotion(
+    time: Res<Time>,
+    mut enemies: Query<
+        (
+            Entity,
+            &Transform,
+            &mut LinearVelocity,
+            &mut Rotation,
+            &mut Flying,
+            &mut LastGroundedPosition,
+            &EnemyBrain,
+            &EnemyBehaviour,
+            &Perception,
+            Option<&TimeDilation>,
+            Option<&AiLod>,
+            Has<AttackToken>,
+        ),
+        (With<Enemy>, Without<Charging>, Without<Knockback>),
+    >,
+    player: Single<&Transform, With<Player>>,
+    camera: Single<&GlobalTransform, With<Camera3d>>,
+    spatial_query: SpatialQuery,
+    colliders: Query<&ColliderOf>,
+    bodies: Query<&RigidBody>,
+) {
+    let mut rng = rand::rng();
+
+    for (entity, transform, mut linear_velocity, mut rotation, mut flying, mut last_grounded, brain, behaviour, perception, dilation, lod, has_token) in enemies.iter_mut() {
+        if lod.is_some_and(|lod| lod.level == LodLevel::Asleep) {
+            continue;
+        }
+        let time_scale = dilation.map_or(1.0, |d| d.scale);
+        let dt = time.delta_secs() * time_scale;
+        let position = transform.translation;
+        let settings = flying.settings.clone();
+        let only_static = |hit: Entity| {
+            let body = colliders.get(hit).map_or(hit, |c| c.body);
+            body != entity && bodies.get(body).is_ok_and(|rb| rb.is_static())
+        };
+
+        // Suelo debajo: si no hay, cae
+        let ground = spatial_query
+            .cast_ray_predicate(position, Dir3::NEG_Y, MAX_GROUND_DISTANCE, true, &SpatialQueryFilter::default(), &only_static)
+            .map(|hit| position.y - hit.distance);
+        let Some(ground_y) = ground else {
+            linear_velocity.y += GRAVITY * dt;
+            continue;
+        };
+        last_grounded.0 = position.with_y(ground_y);
+        let hover_y = ground_y + settings.hover_height;
+
+        let to_player = (player.translation - position).with_y(0.0);
+        let (horizontal, facing, target_y) = match brain.state {
+            EnemyState::Idle | EnemyState::Patrol => {
+                flying.change_target_timer.tick(std::time::Duration::from_secs_f32(dt));
+                let arrived = flying.wander_target.xz().distance(position.xz()) < 1.0;
+                if flying.change_target_timer.just_finished() || arrived {
+                    let offset = Vec2::new(rng.random_range(-1.0..1.0_f32), rng.random_range(-1.0..1.0_f32))
+                        * settings.wander_radius;
+                    flying.wander_target = brain.home + Vec3::new(offset.x, 0.0, offset.y);
+                }
+                let dir = (flying.wander_target - position).with_y(0.0).normalize_or_zero();
+                (dir * settings.wander_speed, dir, hover_y)
+            }
+            EnemyState::Chase => {
+                let velocity = if has_token {
+                    // Con token se acerca hasta el rango del picado
+                    to_player.normalize_or_zero() * behaviour.chase_speed
+                } else if to_player.length() <= behaviour.circle_range * 1.2 {
+                    enemy_ai::waiting_velocity(
+                        WaitingTactic::for_enemy(entity),
+                        position,
+                        player.translation,
+                        camera.forward().as_vec3(),
+                        behaviour,
+                    )
+                    .normalize_or_zero()
+                        * settings.strafe_speed
+                } else {
+                    to_player.normalize_or_zero() * behaviour.chase_speed
+                };
+                // Sube por encima del jugador para tener ángulo de picado
+                (velocity, to_player.normalize_or_zero(), hover_y.max(player.translation.y + settings.hover_height))
+            }
+            EnemyState::Search => {
+                let to_last_known = perception.last_known.map_or(Vec3::ZERO, |p| (p - position).with_y(0.0));
+                let dir = if to_last_known.length() > 1.5 { to_last_known.normalize_or_zero() } else { Vec3::ZERO };
+                (dir * behaviour.walk_speed, dir, hover_y)
+            }
+            // Se eleva un poco antes del picado
+            EnemyState::Windup => (Vec3::ZERO, to_player.normalize_or_zero(), hover_y + 1.0),
+            EnemyState::Alert | EnemyState::Recover => (Vec3::ZERO, to_player.normalize_or_zero(), hover_y),
+            _ => (Vec3::ZERO, Vec3::ZERO, hover_y),
+        };
+
+        let horizontal = avoid_geometry(position, horizontal, settings.avoid_distance, &spatial_query, &only_static);
+        let vertical = ((target_y - position.y) * ALTITUDE_GAIN).clamp(-behaviour.chase_speed, behaviour.chase_speed);
+
+        linear_velocity.0 = horizontal.with_y(vertical) * time_scale;
+        if facing.length_squared() > 0.001 {
+            *rotation = Quat::from_rotation_y((-facing.x).atan2(-facing.z)).into();
+        }
+    }
+}
+
+/// Si hay algo delante, prueba a girar a los lados o a subir
+fn avoid_geometry(
+    position: Vec3,
+    velocity: Vec3,
+    distance: f32,
+    spatial_query: &SpatialQuery,
+    only_static: &dyn Fn(Entity) -> bool,
+) -> Vec3 {
+    let speed = velocity.length();
+    let Ok(forward) = Dir3::new(velocity) else { return velocity; };
+    let blocked = |dir: Dir3| {
+        spatial_query
+            .cast_ray_predicate(position, dir, distance + BODY_RADIUS, true, &SpatialQueryFilter::default(), only_static)
+            .is_some()
+    };
+    if !blocked(forward) {
+        return velocity;
+    }
+    for angle in [45.0_f32, -45.0, 90.0, -90.0] {
+        let dir = Quat::from_rotation_y(angle.to_radians()) * forward;
+        if !blocked(dir) {
+            return dir * speed;
+        }
+    }
+    Vec3::Y * speed
+}
+
+/// Picado en línea recta. Si da con el suelo o una pared, se queda aturdido.
+fn dive_tick(
+    time: Res<Time>,
+    mut enemies: Query<
+        (Entity, &Transform, &EnemyBehaviour, &mut Charging, &mut LinearVelocity, &mut Rotation, Option<&TimeDilation>),
+        With<Flying>,
+    >,
+    spatial_query: SpatialQuery,
+    colliders: Query<&ColliderOf>,
+    bodies: Query<&RigidBody>,
+) {
+    for (entity, transform, behaviour, mut charging, mut linear_velocity, mut rotation, dilation) in enemies.iter_mut() {
+        let EnemyAttack::Dive(settings) = &behaviour.attack else { continue; };
+        if charging.outcome.is_some() {
+            linear_velocity.0 = Vec3::ZERO;
+            continue;
+        }
+        let time_scale = dilation.map_or(1.0, |d| d.scale);
+        let Ok(dir) = Dir3::new(charging.dir) else {
+            charging.outcome = Some(ChargeOutcome::HitWall);
+            continue;
+        };
+
+        let look_ahead = BODY_RADIUS + settings.speed * time.delta_secs() * time_scale + 0.1;
+        let crashed = spatial_query
+            .cast_ray_predicate(transform.translation, dir, look_ahead, true, &SpatialQueryFilter::default(), &|hit| {
+                let body = colliders.get(hit).map_or(hit, |c| c.body);
+                body != entity && bodies.get(body).is_ok_and(|rb| rb.is_static())
+            })
+            .is_some();
+        if crashed {
+            charging.outcome = Some(ChargeOutcome::HitWall);
+            linear_velocity.0 = Vec3::ZERO;
+            continue;
+        }
+
+        charging.speed = settings.speed;
+        linear_velocity.0 = *dir * settings.speed * time_scale;
+        let flat = dir.with_y(0.0);
+        if flat.length_squared() > 0.001 {
+            *rotation = Quat::from_rotation_y((-flat.x).atan2(-flat.z)).into();
+        }
+    }
+}
@@ -0,0 +1,273 @@
+//! Agarrar, cargar y lanzar cualquier objeto físico
+//!
+//! Cualquier cuerpo dinámico con `Grabbable` se puede coger con F mirando
+//! hacia él. Se sostiene con un `DistanceJoint` elástico contra un ancla
+//! cinemática delante de la cámara, así que sigue chocando con el mundo
+//! mientras lo llevas. Se suelta con F, al chocar fuerte o si se queda
+//! enganchado; RMB lo lanza con una fuerza que depende de su masa.
+
+use avian3d::prelude::*;
+use bevy::prelude::*;
+
+use crate::PausableSystems;
+use crate::screens::Screen;
+use crate::screens::gameplay::{Level, player::Player, spawn_level};
+
+pub struct GrabPlugin;
+
+const GRAB_RANGE: f32 = 3.0;
+/// Distancia delante de la cámara a la que se sostiene el objeto
+const HOLD_DISTANCE: f32 = 1.6;
+/// Holgura del joint: por debajo de esto el objeto no tira
+const HOLD_SLACK: f32 = 0.05;
+const HOLD_COMPLIANCE: f32 = 0.002;
+const HOLD_DAMPING: f32 = 8.0;
+/// Lo que se puede alejar el objeto del ancla, más de lo que ya estaba al
+/// cogerlo, antes de soltarse por quedarse enganchado
+const BREAK_MARGIN: f32 = 1.0;
+/// Velocidad a partir de la cual un choque hace soltar el objeto
+const DROP_IMPACT_SPEED: f32 = 9.0;
+/// Impulso del lanzamiento: objetos ligeros salen más rápido
+const THROW_IMPULSE: f32 = 30.0;
+const THROW_MIN_SPEED: f32 = 6.0;
+const THROW_MAX_SPEED: f32 = 20.0;
+
+impl Plugin for GrabPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Grabbable>();
+        app.add_message::<PropThrown>();
+        app.add_systems(OnEnter(Screen::Gameplay), spawn_grab_anchor.after(spawn_level));
+        app.add_systems(
+            Update,
+            (
+                tag_grabbable_props,
+                move_grab_anchor,
+                grab_or_drop,
+                throw_held,
+                drop_on_impact,
+                drop_when_stretched,
+            )
+                .chain()
+                .in_set(PausableSystems)
+                .run_if(in_state(Screen::Gameplay)),
+        );
+    }
+}
+
+// -----------------------------------------------
+// COMPONENTES
+// -----------------------------------------------
+
+/// Se puede coger y lanzar. Reflejado para poder marcarlo desde Blender.
+#[derive(Component, Reflect, Default)]
+#[reflect(Component, Default)]
+pub struct Grabbable;
+
+/// El objeto que el jugador lleva ahora mismo
+#[derive(Component)]
+pub struct Held {
+    joint: Entity,
+    /// Distancia al ancla a partir de la cual se suelta
+    break_distance: f32,
+    /// Lo que tenía el objeto antes de cogerlo, para dejarlo igual al soltar
+    linear_damping: Option<LinearDamping>,
+    angular_damping: Option<AngularDamping>,
+    had_collision_events: bool,
+}
+
+/// Ancla cinemática que sigue a la cámara
+#[derive(Component)]
+struct GrabAnchor;
+
+/// Se lanzó un objeto — otros sistemas (el reloj) reaccionan a esto
+#[derive(Message)]
+pub struct PropThrown {
+    pub entity: Entity,
+}
+
+// -----------------------------------------------
+// SETUP
+// -----------------------------------------------
+
+fn spawn_grab_anchor(mut commands: Commands, level: Single<Entity, With<Level>>) {
+    commands.entity(*level).with_child((
+        Name::new("GrabAnchor"),
+        GrabAnchor,
+        RigidBody::Kinematic,
+        Transform::default(),
+    ));
+}
+
+/// Props del nivel que se pueden coger sin marcarlos a mano en Blender
+fn tag_grabbable_props(
+    mut commands: Commands,
+    bodies: Query<(Entity, &Name, &RigidBody), (Added<RigidBody>, Without<Grabbable>)>,
+) {
+    for (entity, name, body) in bodies.iter() {
+        let name = name.as_str().to_lowercase();
+        if body.is_dynamic() && (name.contains("flower") || name.contains("gas_tank")) {
+            commands.entity(entity).insert(Grabbable);
+        }
+    }
+}
+
+fn move_grab_anchor(
+    camera: Single<&GlobalTransform, With<Camera3d>>,
+    mut anchor: Single<&mut Position, With<GrabAnchor>>,
+) {
+    anchor.0 = camera.translation() + camera.forward() * HOLD_DISTANCE;
+}
+
+// -----------------------------------------------
+// COGER / SOLTAR
+// -----------------------------------------------
+
+fn grab_or_drop(
+    mut commands: Commands,
+    keyboard: Res<ButtonInput<KeyCode>>,
+    camera: Single<&GlobalTransform, With<Camera3d>>,
+    player: Single<Entity, With<Player>>,
+    anchor: Single<(Entity, &Position), With<GrabAnchor>>,
+    spatial_query: SpatialQuery,
+    collider_of: Query<&ColliderOf>,
+    grabbables: Query<
+        (&Position, Option<&LinearDamping>, Option<&AngularDamping>, Has<CollisionEventsEnabled>),
+        (With<Grabbable>, Without<Held>, Without<GrabAnchor>),
+    >,
+    held: Query<(Entity, &Held)>,
+) {
+    if !keyboard.just_pressed(KeyCode::KeyF) {
+        return;
+    }
+
+    if let Ok((entity, held)) = held.single() {
+        release(&mut commands, entity, held);
+        return;
+    }
+
+    let Some(hit) = spatial_query.cast_ray(
+        camera.translation(),
+        camera.forward(),
+        GRAB_RANGE,
+        true,
+        &SpatialQueryFilter::from_excluded_entities([*player]),
+    ) else {
+        return;
+    };
+
+    let body = collider_of.get(hit.entity).map_or(hit.entity, |c| c.body);
+    let Ok((position, linear_damping, angular_damping, had_collision_events)) = grabbables.get(body) else {
+        return;
+    };
+    let (anchor, anchor_position) = *anchor;
+
+    let joint = commands
+        .spawn((
+            Name::new("GrabJoint"),
+            DespawnOnExit(Screen::Gameplay),
+            DistanceJoint::new(anchor, body)
+                .with_limits(0.0, HOLD_SLACK)
+                .with_compliance(HOLD_COMPLIANCE),
+        ))
+        .id();
+    commands.entity(body).insert((
+        Held {
+            joint,
+            // Cogido desde lejos, el centro ya empieza lejos del ancla
+            break_distance: position.distance(anchor_position.0).max(GRAB_RANGE - HOLD_DISTANCE) + BREAK_MARGIN,
+            linear_damping: linear_damping.copied(),
+            angular_damping: angular_damping.copied(),
+            had_collision_events,
+        },
+        LinearDamping(HOLD_DAMPING),
+        AngularDamping(HOLD_DAMPING),
+        CollisionEventsEnabled,
+    ));
+    info!("Objeto agarrado: {:?}", body);
+}
+
+pub fn release(commands: &mut Commands, entity: Entity, held: &Held) {
+    commands.entity(held.joint).despawn();
+    let mut prop = commands.entity(entity);
+    prop.remove::<(Held, LinearDamping, AngularDamping)>();
+    if let Some(damping) = held.linear_damping {
+        prop.insert(damping);
+    }
+    if let Some(damping) = held.angular_damping {
+        prop.insert(damping);
+    }
+    if !held.had_collision_events {
+        prop.remove::<CollisionEventsEnabled>();
+    }
+}
+
+// -----------------------------------------------
+// LANZAR
+// -----------------------------------------------
+
+pub(super) fn throw_held(
+    mut commands: Commands,
+    mouse: Res<ButtonInput<MouseButton>>,
+    camera: Single<&GlobalTransform, With<Camera3d>>,
+    mut held: Query<(Entity, &Held, &ComputedMass, &mut LinearVelocity, &mut AngularVelocity)>,
+    mut thrown_writer: MessageWriter<PropThrown>,
+) {
+    if !mouse.just_pressed(MouseButton::Right) {
+        return;
+    }
+    let Ok((entity, held, mass, mut linear_velocity, mut angular_velocity)) = held.single_mut() else {
+        return;
+    };
+
+    let speed = (THROW_IMPULSE / mass.value().max(0.1)).clamp(THROW_MIN_SPEED, THROW_MAX_SPEED);
+    linear_velocity.0 = camera.forward() * speed + Vec3::Y * 3.0;
+    angular_velocity.0 = Vec3::new(5.0, 3.0, 2.0);
+
+    release(&mut commands, entity, held);
+    thrown_writer.write(PropThrown { entity });
+    info!("Objeto lanzado a {speed:.1} m/s");
+}
+
+// -----------------------------------------------
+// SOLTAR AL CHOCAR
+// -----------------------------------------------
+
+fn drop_on_impact(
+    mut commands: Commands,
+    mut collisions: MessageReader<CollisionStart>,
+    held: Query<(Entity, &Held, &LinearVelocity)>,
+    players: Query<(), With<Player>>,
+) {
+    for ev in collisions.read() {
+        let (Some(body1), Some(body2)) = (ev.body1, ev.body2) else { continue; };
+        let (held_body, other) = if held.contains(body1) {
+            (body1, body2)
+        } else if held.contains(body2) {
+            (body2, body1)
+        } else {
+            continue;
+        };
+        if players.contains(other) {
+            continue;
+        }
+
+        let Ok((entity, held, velocity)) = held.get(held_body) else { continue; };
+        if velocity.length() >= DROP_IMPACT_SPEED {
+            release(&mut commands, entity, held);
+            info!("Objeto soltado por impacto");
+        }
+    }
+}
+
+fn drop_when_stretched(
+    mut commands: Commands,
+    anchor: Single<&Position, With<GrabAnchor>>,
+    held: Query<(Entity, &Held, &Position), Without<GrabAnchor>>,
+) {
+    for (entity, held, position) in held.iter() {
+        if position.distance(anchor.0) > held.break_distance {
+            release(&mut commands, entity, held);
+            info!("Objeto soltado: se quedó enganchado");
+        }
+    }
+}
@@ -0,0 +1,214 @@
+//! Barras de vida de los enemigos
+//!
+//! Cada enemigo tiene una barra de UI que sigue su cabeza en pantalla. Solo
+//! se ve un rato después de recibir un golpe o mientras el jugador lo está
+//! apuntando, y se esconde si está detrás de la cámara, fuera de pantalla o
+//! tapado por el escenario. La barra cuelga del enemigo con una relación
+//! (`HealthBarOf` / `HealthBars`), así que se va con él al despawnearlo.
+//! Debajo de la vida va una línea amarilla con la poise que le queda.
+
+use avian3d::prelude::*;
+use bevy::prelude::*;
+
+use crate::screens::Screen;
+use crate::screens::gameplay::{enemy::Enemy, hitstop::MeleeImpact, player::Player, poise::Poise};
+
+pub struct HealthBarPlugin;
+
+/// Segundos que sigue visible después de un golpe o de dejar de apuntarlo
+const SHOW_TIME: f32 = 3.0;
+/// Alcance de "apuntar" a un enemigo con la cámara
+const TARGET_RANGE: f32 = 25.0;
+/// Altura sobre los pies del enemigo donde va la barra
+const BAR_HEIGHT: f32 = 3.2;
+const BAR_WIDTH: f32 = 50.0;
+
+impl Plugin for HealthBarPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (reveal_on_hit, reveal_on_target, update_health_bars)
+                .chain()
+                .run_if(in_state(Screen::Gameplay)),
+        );
+    }
+}
+
+/// La barra de vida de un enemigo
+#[derive(Component)]
+#[relationship(relationship_target = HealthBars)]
+pub struct HealthBarOf(pub Entity);
+
+/// Barras de vida que cuelgan de este enemigo
+#[derive(Component, Default)]
+#[relationship_target(relationship = HealthBarOf, linked_spawn)]
+pub struct HealthBars(Vec<Entity>);
+
+#[derive(Component, Default)]
+pub struct HealthBar {
+    /// Tiempo que le queda visible
+    visible_for: f32,
+}
+
+#[derive(Component)]
+struct HealthBarFill;
+
+#[derive(Component)]
+struct PoiseBarFill;
+
+pub fn spawn_health_bar(commands: &mut Commands, enemy: Entity, name: String) {
+    commands.spawn((
+        Name::new(name),
+        HealthBarOf(enemy),
+        HealthBar::default(),
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Px(BAR_WIDTH),
+            height: Val::Px(9.0),
+            padding: UiRect::all(Val::Px(1.0)),
+            row_gap: Val::Px(1.0),
+            flex_direction: FlexDirection::Column,
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.05, 0.0, 0.05, 0.8)),
+        Visibility::Hidden,
+        ZIndex(10),
+        // Por si su enemigo sobrevive a la pantalla
+        DespawnOnExit(Screen::Gameplay),
+        children![
+            (
+                HealthBarFill,
+                Node {
+                    width: Val::Percent(100.0),
+                    flex_grow: 1.0,
+                    ..default()
+                },
+                BackgroundColor(bevy::color::palettes::css::RED.into()),
+            ),
+            (
+                PoiseBarFill,
+                Node {
+                    width: Val::Percent(100.0),
+                    height: Val::Px(2.0),
+                    ..default()
+                },
+                BackgroundColor(bevy::color::palettes::css::GOLD.into()),
+            ),
+        ],
+    ));
+}
+
+fn reveal_on_hit(
+    mut impacts: MessageReader<MeleeImpact>,
+    enemies: Query<&HealthBars>,
+    mut bars: Query<&mut HealthBar>,
+) {
+    for impact in impacts.read() {
+        let Ok(enemy_bars) = enemies.get(impact.enemy) else { continue; };
+        for bar in enemy_bars.iter() {
+            if let Ok(mut bar) = bars.get_mut(bar) {
+                bar.visible_for = SHOW_TIME;
+            }
+        }
+    }
+}
+
+/// El enemigo al que apunta la cámara enseña su barra
+fn reveal_on_target(
+    camera: Single<&GlobalTransform, With<Camera3d>>,
+    player: Single<Entity, With<Player>>,
+    spatial_query: SpatialQuery,
+    colliders: Query<&ColliderOf>,
+    enemies: Query<&HealthBars, With<Enemy>>,
+    mut bars: Query<&mut HealthBar>,
+) {
+    let Some(hit) = spatial_query.cast_ray_predicate(
+        camera.translation(),
+        camera.forward(),
+        TARGET_RANGE,
+        true,
+        &SpatialQueryFilter::default(),
+        &|hit| colliders.get(hit).map_or(hit, |c| c.body) != *player,
+    ) else {
+        return;
+    };
+    let body = colliders.get(hit.entity).map_or(hit.entity, |c| c.body);
+    let Ok(enemy_bars) = enemies.get(body) else { return; };
+    for bar in enemy_bars.iter() {
+        if let Ok(mut bar) = bars.get_mut(bar) {
+            bar.visible_for = SHOW_TIME;
+        }
+    }
+}
+
+fn update_health_bars(
+    time: Res<Time>,
+    camera: Single<(&Camera, &GlobalTransform), With<Camera3d>>,
+    player: Single<Entity, With<Player>>,
+    spatial_query: SpatialQuery,
+    colliders: Query<&ColliderOf>,
+    enemies: Query<(&Enemy, &Transform, Option<&Poise>)>,
+    mut bars: Query<
+        (&HealthBarOf, &mut HealthBar, &mut Node, &mut Visibility, &Children),
+        (Without<HealthBarFill>, Without<PoiseBarFill>),
+    >,
+    mut fills: Query<&mut Node, (With<HealthBarFill>, Without<PoiseBarFill>)>,
+    mut poise_fills: Query<&mut Node, With<PoiseBarFill>>,
+) {
+    let (camera, camera_transform) = *camera;
+    let viewport = camera.logical_viewport_size().unwrap_or(Vec2::ZERO);
+
+    for (bar_of, mut bar, mut node, mut visibility, children) in bars.iter_mut() {
+        bar.visible_for -= time.delta_secs();
+        let Ok((enemy, transform, poise)) = enemies.get(bar_of.0) else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+
+        let head = transform.translation + Vec3::Y * BAR_HEIGHT;
+        let on_screen = camera
+            .world_to_viewport(camera_transform, head)
+            .ok()
+            .filter(|p| p.x >= 0.0 && p.y >= 0.0 && p.x <= viewport.x && p.y <= viewport.y);
+
+        // Tapado: algo que no es el propio enemigo entre la cámara y la barra
+        let occluded = || {
+            let to_head = head - camera_transform.translation();
+            Dir3::new(to_head).is_ok_and(|dir| {
+                spatial_query
+                    .cast_ray_predicate(
+                        camera_transform.translation(),
+                        dir,
+                        to_head.length(),
+                        true,
+                        &SpatialQueryFilter::default(),
+                        &|hit| {
+                            let body = colliders.get(hit).map_or(hit, |c| c.body);
+                            body != bar_of.0 && body != *player && !enemies.contains(body)
+                        },
+                    )
+                    .is_some()
+            })
+        };
+
+        let Some(screen_pos) = on_screen.filter(|_| bar.visible_for > 0.0 && !occluded()) else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+
+        *visibility = Visibility::Inherited;
+        node.left = Val::Px(screen_pos.x - BAR_WIDTH * 0.5);
+        node.top = Val::Px(screen_pos.y);
+
+        let fraction = enemy.health as f32 / enemy.max_health.max(1) as f32;
+        let poise_fraction = poise.map_or(0.0, Poise::fraction);
+        for child in children.iter() {
+            if let Ok(mut fill) = fills.get_mut(child) {
+                fill.width = Val::Percent(fraction * 100.0);
+            }
+            if let Ok(mut fill) = poise_fills.get_mut(child) {
+                fill.width = Val::Percent(poise_fraction * 100.0);
+            }
+        }
+    }
+}
@@ -0,0 +1,143 @@
+//! Hitstop — congela unos frames la animación al conectar un golpe
+//!
+//! `attack` escribe un `MeleeImpact` por cada enemigo golpeado. Aquí se
+//! juntan todos los impactos del frame en UN solo hitstop (nunca se apilan),
+//! se pausan las animaciones del arma y de los enemigos golpeados, y se
+//! baja un poco `Time<Virtual>` encima de la escala del fever slow-mo.
+
+use bevy::prelude::*;
+
+use crate::PausableSystems;
+use crate::screens::Screen;
+use crate::screens::gameplay::combo_system::{self, SlowMotionState};
+use crate::screens::gameplay::enemy::EnemyAnimationPlayer;
+use crate::screens::gameplay::weapons::WeaponAnimationPlayer;
+
+pub struct HitstopPlugin;
+
+/// Duración mínima (segundos reales) de cualquier hitstop
+const HITSTOP_BASE: f32 = 0.04;
+/// Segundos extra por punto de daño
+const HITSTOP_PER_DAMAGE: f32 = 0.03;
+/// Extra para el golpe que mata
+const HITSTOP_LETHAL_BONUS: f32 = 0.05;
+/// Extra para el golpe que rompe la poise
+const HITSTOP_STAGGER_BONUS: f32 = 0.03;
+const HITSTOP_MAX: f32 = 0.15;
+/// Escala de `Time<Virtual>` mientras dura el hitstop
+const HITSTOP_TIME_SCALE: f32 = 0.85;
+
+impl Plugin for HitstopPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<MeleeImpact>();
+        app.init_resource::<Hitstop>();
+        app.add_systems(
+            Update,
+            (start_hitstop, tick_hitstop)
+                .chain()
+                .after(combo_system::tick_slow_motion)
+                .in_set(PausableSystems)
+                .run_if(in_state(Screen::Gameplay)),
+        );
+        app.add_systems(OnExit(Screen::Gameplay), reset_hitstop);
+    }
+}
+
+/// Un golpe cuerpo a cuerpo que conectó con un enemigo
+#[derive(Message)]
+pub struct MeleeImpact {
+    pub enemy: Entity,
+    pub damage: u32,
+    pub lethal: bool,
+    /// Rompió la poise: el enemigo sale despedido y queda aturdido
+    pub staggered: bool,
+}
+
+impl MeleeImpact {
+    fn duration(&self) -> f32 {
+        let lethal = if self.lethal { HITSTOP_LETHAL_BONUS } else { 0.0 };
+        let stagger = if self.staggered && !self.lethal { HITSTOP_STAGGER_BONUS } else { 0.0 };
+        (HITSTOP_BASE + HITSTOP_PER_DAMAGE * self.damage as f32 + lethal + stagger).min(HITSTOP_MAX)
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct Hitstop {
+    pub remaining: f32,
+    /// Entidades con `AnimationPlayer` pausadas por este hitstop
+    paused: Vec<Entity>,
+}
+
+impl Hitstop {
+    pub fn is_active(&self) -> bool {
+        self.remaining > 0.0
+    }
+}
+
+fn base_time_speed(slow_mo: Option<&SlowMotionState>) -> f32 {
+    slow_mo.map_or(1.0, |s| s.scale)
+}
+
+fn start_hitstop(
+    mut impacts: MessageReader<MeleeImpact>,
+    mut hitstop: ResMut<Hitstop>,
+    mut anim_players: Query<(Entity, &mut AnimationPlayer, Option<&EnemyAnimationPlayer>, Has<WeaponAnimationPlayer>)>,
+) {
+    let mut duration: f32 = 0.0;
+    let mut hit_enemies = Vec::new();
+    for impact in impacts.read() {
+        duration = duration.max(impact.duration());
+        hit_enemies.push(impact.enemy);
+    }
+    if duration <= 0.0 {
+        return;
+    }
+
+    // Varios enemigos en un mismo swing → un solo hitstop, el más largo
+    hitstop.remaining = hitstop.remaining.max(duration);
+
+    for (entity, mut player, enemy_anim, is_weapon) in anim_players.iter_mut() {
+        let hit = enemy_anim.is_some_and(|ap| hit_enemies.contains(&ap.enemy));
+        if !(hit || is_weapon) || hitstop.paused.contains(&entity) {
+            continue;
+        }
+        player.pause_all();
+        hitstop.paused.push(entity);
+    }
+}
+
+fn tick_hitstop(
+    mut hitstop: ResMut<Hitstop>,
+    mut anim_players: Query<&mut AnimationPlayer>,
+    mut virtual_time: ResMut<Time<Virtual>>,
+    slow_mo: Option<Res<SlowMotionState>>,
+    time: Res<Time<Real>>,
+) {
+    if !hitstop.is_active() {
+        return;
+    }
+
+    let base = base_time_speed(slow_mo.as_deref());
+    hitstop.remaining -= time.delta_secs();
+
+    if hitstop.remaining > 0.0 {
+        virtual_time.set_relative_speed(base * HITSTOP_TIME_SCALE);
+        return;
+    }
+
+    hitstop.remaining = 0.0;
+    virtual_time.set_relative_speed(base);
+    for entity in hitstop.paused.drain(..) {
+        // El enemigo pudo morir durante el hitstop
+        if let Ok(mut player) = anim_players.get_mut(entity) {
+            player.resume_all();
+        }
+    }
+}
+
+fn reset_hitstop(mut hitstop: ResMut<Hitstop>, mut virtual_time: ResMut<Time<Virtual>>) {
+    if hitstop.is_active() {
+        virtual_time.set_relative_speed(1.0);
+    }
+    *hitstop = Hitstop::default();
+}
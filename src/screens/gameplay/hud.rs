@@ -1,7 +1,12 @@
 use bevy::prelude::*;
 use crate::screens::Screen;
 use super::player::Player;
+use super::events::EnemyKilledEvent;
+use super::alarm_clock::ClockCharges;
+use super::boss::Boss;
 use super::enemy::Enemy;
+use super::arena::{Arena, ArenaRun, ArenaState};
+use super::spawn_enemy_waves::WaveDirector;
 
 pub struct HudPlugin;
 
@@ -9,7 +14,7 @@ impl Plugin for HudPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(KillStreak::default());
         app.insert_resource(CirclesSpawned::default());
-        app.add_systems(OnEnter(Screen::Gameplay), spawn_overlays);
+        app.add_systems(OnEnter(Screen::Gameplay), (spawn_overlays, reset_streak));
         app.add_systems(
             Update,
             (
@@ -19,6 +24,9 @@ impl Plugin for HudPlugin {
                 update_hallucination_overlay,
                 spawn_hallucination_circles,
                 update_hallucination_circles,
+                update_gadget_slot,
+                update_boss_bar,
+                update_arena_banner,
             )
                 .run_if(in_state(Screen::Gameplay)),
         );
@@ -48,6 +56,27 @@ struct DamageOverlay;
 #[derive(Component)]
 struct HallucinationOverlay;
 
+/// Hueco del gadget que lleva el jugador (relojes)
+#[derive(Component)]
+struct GadgetSlot;
+
+#[derive(Component)]
+struct GadgetCountText;
+
+/// Barra de vida del jefe — arriba en el centro, solo con un jefe vivo
+#[derive(Component)]
+struct BossBar;
+
+#[derive(Component)]
+struct BossBarFill;
+
+#[derive(Component)]
+struct BossBarTitle;
+
+/// Cuenta atrás, tiempo y oleada de la arena en curso — bajo la barra del jefe
+#[derive(Component)]
+struct ArenaBanner;
+
 #[derive(Component)]
 struct HallucinationCircle {
     speed_x: f32,
@@ -111,6 +140,106 @@ fn spawn_overlays(mut commands: Commands, asset_server: Res<AssetServer>) {
         ZIndex(12),
         GlobalZIndex(12),
     ));
+
+    // Hueco del gadget — abajo a la derecha, invisible sin relojes
+    commands.spawn((
+        Name::new("GadgetSlot"),
+        GadgetSlot,
+        Node {
+            position_type: PositionType::Absolute,
+            right: Val::Px(32.0),
+            bottom: Val::Px(32.0),
+            width: Val::Px(96.0),
+            height: Val::Px(96.0),
+            align_items: AlignItems::End,
+            justify_content: JustifyContent::End,
+            border_radius: BorderRadius::all(Val::Percent(50.0)),
+            ..default()
+        },
+        BackgroundColor(Color::srgba(0.0, 0.0, 0.0, 0.35)),
+        ImageNode {
+            image: asset_server.load("images/clock_field.png"),
+            color: Color::srgba(1.0, 1.0, 1.0, 0.0),
+            ..default()
+        },
+        Visibility::Hidden,
+        ZIndex(14),
+        GlobalZIndex(14),
+        DespawnOnExit(Screen::Gameplay),
+        children![(
+            GadgetCountText,
+            Text::new(""),
+            TextFont { font_size: 24.0, ..default() },
+            TextColor(Color::srgb(1.0, 0.75, 0.1)),
+        )],
+    ));
+
+    // Barra del jefe — arriba en el centro, invisible sin jefe
+    commands.spawn((
+        Name::new("BossBar"),
+        BossBar,
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(24.0),
+            width: Val::Percent(100.0),
+            flex_direction: FlexDirection::Column,
+            align_items: AlignItems::Center,
+            row_gap: Val::Px(6.0),
+            ..default()
+        },
+        Visibility::Hidden,
+        ZIndex(14),
+        GlobalZIndex(14),
+        DespawnOnExit(Screen::Gameplay),
+        children![
+            (
+                BossBarTitle,
+                Text::new(""),
+                TextFont { font_size: 22.0, ..default() },
+                TextColor(Color::srgb(1.0, 0.85, 0.9)),
+            ),
+            (
+                Node {
+                    width: Val::Percent(50.0),
+                    height: Val::Px(14.0),
+                    padding: UiRect::all(Val::Px(2.0)),
+                    ..default()
+                },
+                BackgroundColor(Color::srgba(0.05, 0.0, 0.05, 0.8)),
+                children![(
+                    BossBarFill,
+                    Node {
+                        width: Val::Percent(100.0),
+                        height: Val::Percent(100.0),
+                        ..default()
+                    },
+                    BackgroundColor(Color::srgb(0.85, 0.1, 0.35)),
+                )],
+            ),
+        ],
+    ));
+
+    // Arena — bajo la barra del jefe, invisible fuera de una arena
+    commands.spawn((
+        Name::new("ArenaBanner"),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(80.0),
+            width: Val::Percent(100.0),
+            justify_content: JustifyContent::Center,
+            ..default()
+        },
+        ZIndex(14),
+        GlobalZIndex(14),
+        DespawnOnExit(Screen::Gameplay),
+        children![(
+            ArenaBanner,
+            Text::new(""),
+            TextFont { font_size: 36.0, ..default() },
+            TextColor(Color::srgb(1.0, 0.9, 0.6)),
+            Visibility::Hidden,
+        )],
+    ));
 }
 
 fn spawn_hallucination_circles(
@@ -178,6 +307,77 @@ fn update_damage_overlay(
     overlay.color = Color::srgba(1.0, 1.0, 1.0, damage_amount * 0.85);
 }
 
+fn update_gadget_slot(
+    charges: Single<&ClockCharges, Changed<ClockCharges>>,
+    slot: Single<(&mut Visibility, &mut ImageNode), With<GadgetSlot>>,
+    mut text: Single<&mut Text, With<GadgetCountText>>,
+) {
+    let (mut visibility, mut image) = slot.into_inner();
+    if charges.charges == 0 {
+        *visibility = Visibility::Hidden;
+        return;
+    }
+    *visibility = Visibility::Inherited;
+    image.color = Color::WHITE;
+    text.0 = format!("{}/{}", charges.charges, charges.max);
+}
+
+fn update_boss_bar(
+    bosses: Query<(&Enemy, &Boss)>,
+    mut bar: Single<&mut Visibility, With<BossBar>>,
+    mut fill: Single<&mut Node, With<BossBarFill>>,
+    mut title: Single<&mut Text, With<BossBarTitle>>,
+) {
+    let Some((enemy, boss)) = bosses.iter().next() else {
+        **bar = Visibility::Hidden;
+        return;
+    };
+    **bar = Visibility::Inherited;
+    let fraction = enemy.health as f32 / enemy.max_health.max(1) as f32;
+    fill.width = Val::Percent(fraction * 100.0);
+    let phase = boss.phase.min(boss.phase_count().saturating_sub(1)) + 1;
+    title.0 = format!("{} — fase {}/{}", boss.title(), phase, boss.phase_count());
+}
+
+fn update_arena_banner(
+    arenas: Query<(&Arena, &ArenaRun)>,
+    director: Res<WaveDirector>,
+    banner: Single<(&mut Text, &mut Visibility), With<ArenaBanner>>,
+) {
+    let (mut text, mut visibility) = banner.into_inner();
+    let line = arenas.iter().find_map(|(arena, run)| match run.state {
+        ArenaState::Countdown { remaining } => Some(format!("{}", remaining.ceil().max(1.0))),
+        ArenaState::Queued => Some("Esperando...".to_string()),
+        // Las oleadas del director solo son de esta arena si está con su zona
+        ArenaState::Fighting { .. } if director.current_zone() != Some(arena.zone.as_str()) => {
+            Some("Esperando...".to_string())
+        }
+        ArenaState::Fighting { elapsed } => {
+            let wave = if director.is_endless() {
+                format!("Oleada {}", director.current_wave())
+            } else {
+                format!("Oleada {}/{}", director.current_wave(), director.total_waves())
+            };
+            if arena.time_limit > 0.0 {
+                let left = (arena.time_limit - elapsed).max(0.0).ceil() as u32;
+                Some(format!("{wave} — {}:{:02}", left / 60, left % 60))
+            } else {
+                Some(wave)
+            }
+        }
+        ArenaState::Collapsing { .. } => Some("¡El suelo se hunde!".to_string()),
+        ArenaState::Cleared { elapsed } if elapsed < 3.0 => Some("Arena despejada".to_string()),
+        _ => None,
+    });
+    match line {
+        Some(line) => {
+            *visibility = Visibility::Inherited;
+            text.0 = line;
+        }
+        None => *visibility = Visibility::Hidden,
+    }
+}
+
 fn update_hallucination_overlay(
     streak: Res<KillStreak>,
     time: Res<Time>,
@@ -189,18 +389,20 @@ fn update_hallucination_overlay(
     overlay.color = Color::srgba(1.0, 1.0, 1.0, alpha);
 }
 
-fn track_kills(
-    enemies: Query<(), With<Enemy>>,
-    mut enemy_count: Local<u32>,
+/// Cada partida empieza sin racha
+fn reset_streak(mut streak: ResMut<KillStreak>) {
+    *streak = KillStreak::default();
+}
+
+pub(super) fn track_kills(
+    mut killed: MessageReader<EnemyKilledEvent>,
     mut streak: ResMut<KillStreak>,
 ) {
-    let current_count = enemies.iter().count() as u32;
-    if current_count < *enemy_count {
-        let kills_this_frame = *enemy_count - current_count;
+    let kills_this_frame = killed.read().count() as u32;
+    if kills_this_frame > 0 {
         streak.kills += kills_this_frame;
         streak.decay_timer = 6.0;
     }
-    *enemy_count = current_count;
 }
 
 fn decay_streak(
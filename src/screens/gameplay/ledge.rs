@@ -0,0 +1,103 @@
+//! Bordes de las nubes y caídas al vacío
+//!
+//! Los enemigos de suelo persiguen en línea recta (o por un navmesh que no
+//! siempre llega hasta el borde), así que sin nada más se salen de la nube.
+//! Antes de moverse miran con un raycast si hay suelo un poco por delante;
+//! si no lo hay, frenan en el borde. Solo se saltan la comprobación a
+//! propósito: embistiendo (`Charging`) o saliendo despedidos de un golpe
+//! (`Knockback`).
+//!
+//! Si aun así caen, `enemy_health_system` los mata como `DeathCause::Fall` y
+//! la muerte se la lleva quien les dio el último golpe (`LastHitBy`).
+
+use avian3d::prelude::*;
+use bevy::prelude::*;
+
+use crate::screens::Screen;
+use crate::screens::gameplay::{
+    charge_attack::{self, Charging},
+    crowd,
+    enemy::{Enemy, Grounded, Knockback},
+    flying_enemy::Flying,
+};
+
+pub struct LedgePlugin;
+
+/// Cuánto por delante del cuerpo se busca suelo
+const PROBE_AHEAD: f32 = 0.8;
+/// Altura sobre los pies desde la que sale el raycast
+const PROBE_HEIGHT: f32 = 1.0;
+/// Un escalón más alto que esto ya cuenta como borde
+const MAX_STEP_DOWN: f32 = 1.5;
+/// Segundos que dura el crédito de un golpe
+const CREDIT_TIME: f32 = 6.0;
+
+impl Plugin for LedgePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            (stop_at_ledges, forget_last_hit)
+                .after(crowd::separation_steering)
+                .before(charge_attack::charge_tick)
+                .run_if(in_state(Screen::Gameplay)),
+        );
+    }
+}
+
+/// Quién golpeó a este enemigo por última vez. Si se cae en los próximos
+/// segundos, la muerte es suya.
+#[derive(Component)]
+pub struct LastHitBy {
+    pub attacker: Entity,
+    remaining_time: f32,
+}
+
+impl LastHitBy {
+    pub fn new(attacker: Entity) -> Self {
+        Self { attacker, remaining_time: CREDIT_TIME }
+    }
+}
+
+pub(super) fn stop_at_ledges(
+    mut enemies: Query<
+        (Entity, &Transform, &mut LinearVelocity),
+        (With<Enemy>, With<Grounded>, Without<Charging>, Without<Knockback>, Without<Flying>),
+    >,
+    spatial_query: SpatialQuery,
+    colliders: Query<&ColliderOf>,
+    bodies: Query<&RigidBody>,
+) {
+    for (entity, transform, mut linear_velocity) in enemies.iter_mut() {
+        let horizontal = linear_velocity.with_y(0.0);
+        let Ok(dir) = Dir3::new(horizontal) else { continue; };
+
+        let origin = transform.translation + *dir * PROBE_AHEAD + Vec3::Y * PROBE_HEIGHT;
+        let has_ground = spatial_query
+            .cast_ray_predicate(
+                origin,
+                Dir3::NEG_Y,
+                PROBE_HEIGHT + MAX_STEP_DOWN,
+                true,
+                &SpatialQueryFilter::default(),
+                &|hit| {
+                    let body = colliders.get(hit).map_or(hit, |c| c.body);
+                    body != entity && bodies.get(body).is_ok_and(|rb| rb.is_static())
+                },
+            )
+            .is_some();
+
+        if !has_ground {
+            linear_velocity.x = 0.0;
+            linear_velocity.z = 0.0;
+        }
+    }
+}
+
+fn forget_last_hit(mut commands: Commands, time: Res<Time>, mut hits: Query<(Entity, &mut LastHitBy)>) {
+    for (entity, mut last_hit) in hits.iter_mut() {
+        last_hit.remaining_time -= time.delta_secs();
+        if last_hit.remaining_time <= 0.0 {
+            commands.entity(entity).remove::<LastHitBy>();
+        }
+    }
+}
@@ -0,0 +1,301 @@
+//! Loot de los enemigos
+//!
+//! Cada tipo de enemigo lleva un `LootTableId`. Cuando muere (por lo que sea)
+//! llega un `EnemyKilledEvent` y se tira su tabla: drops garantizados, tiradas
+//! con pesos y un contador de pity que fuerza el drop raro si lleva muchas
+//! muertes sin salir. El loot sale disparado con física, se recoge al pasar
+//! por encima y desaparece si nadie lo coge a tiempo.
+
+use avian3d::prelude::*;
+use bevy::{platform::collections::HashMap, prelude::*};
+use rand::RngExt;
+use serde::Deserialize;
+
+use crate::PausableSystems;
+use crate::screens::Screen;
+use crate::screens::gameplay::{
+    LevelAssets,
+    alarm_clock::spawn_alarm_clock,
+    events::EnemyKilledEvent,
+    grab::Held,
+    player::Player,
+    powerups::DamageBoost,
+};
+
+pub struct LootPlugin;
+
+/// Segundos que dura un drop en el suelo
+const LOOT_LIFETIME: f32 = 15.0;
+/// Últimos segundos en los que parpadea antes de desaparecer
+const LOOT_BLINK_TIME: f32 = 3.0;
+const PICKUP_RANGE: f32 = 1.5;
+/// Tiempo tras el spawn en el que aún no se puede recoger (que se vea el salto)
+const PICKUP_DELAY: f32 = 0.4;
+const POP_SPEED: f32 = 5.0;
+const HEALTH_AMOUNT: f32 = 0.25;
+
+impl Plugin for LootPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(LootTables::default());
+        app.init_resource::<LootPity>();
+        app.add_systems(OnExit(Screen::Gameplay), reset_pity);
+        app.add_systems(
+            Update,
+            (drop_loot, collect_loot, claim_held_loot, loot_lifetime)
+                .chain()
+                .in_set(PausableSystems)
+                .run_if(in_state(Screen::Gameplay)),
+        );
+    }
+}
+
+// -----------------------------------------------
+// TABLAS
+// -----------------------------------------------
+
+/// Qué tabla de loot usa un enemigo
+#[derive(Component, Clone, Copy, PartialEq, Eq, Hash, Debug, Deserialize)]
+pub enum LootTableId {
+    Hammerhead,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum LootKind {
+    AlarmClock,
+    Health,
+    DamageBoost,
+}
+
+pub struct LootEntry {
+    /// `None` = esta tirada no da nada
+    pub kind: Option<LootKind>,
+    pub weight: u32,
+}
+
+pub struct LootTable {
+    /// Siempre caen
+    pub guaranteed: Vec<LootKind>,
+    pub rolls: u32,
+    pub entries: Vec<LootEntry>,
+    /// Si pasan `after` muertes sin que salga `kind`, sale seguro
+    pub pity: Option<(LootKind, u32)>,
+}
+
+impl LootTable {
+    fn roll(&self, rng: &mut rand::rngs::ThreadRng) -> Option<LootKind> {
+        let total: u32 = self.entries.iter().map(|e| e.weight).sum();
+        if total == 0 {
+            return None;
+        }
+        let mut pick = rng.random_range(0..total);
+        for entry in &self.entries {
+            if pick < entry.weight {
+                return entry.kind;
+            }
+            pick -= entry.weight;
+        }
+        None
+    }
+}
+
+#[derive(Resource)]
+pub struct LootTables(HashMap<LootTableId, LootTable>);
+
+impl Default for LootTables {
+    fn default() -> Self {
+        let mut tables = HashMap::default();
+        tables.insert(
+            LootTableId::Hammerhead,
+            LootTable {
+                guaranteed: vec![],
+                rolls: 1,
+                entries: vec![
+                    LootEntry { kind: None, weight: 50 },
+                    LootEntry { kind: Some(LootKind::Health), weight: 25 },
+                    LootEntry { kind: Some(LootKind::AlarmClock), weight: 15 },
+                    LootEntry { kind: Some(LootKind::DamageBoost), weight: 10 },
+                ],
+                pity: Some((LootKind::AlarmClock, 6)),
+            },
+        );
+        Self(tables)
+    }
+}
+
+/// Muertes seguidas sin el drop de pity, por tabla
+#[derive(Resource, Default)]
+struct LootPity(HashMap<LootTableId, u32>);
+
+fn reset_pity(mut pity: ResMut<LootPity>) {
+    pity.0.clear();
+}
+
+// -----------------------------------------------
+// DROP
+// -----------------------------------------------
+
+#[derive(Component)]
+pub struct LootPickup {
+    pub kind: LootKind,
+}
+
+/// Tiempo de vida de cualquier drop (incluidos los relojes)
+#[derive(Component)]
+pub struct LootLifetime {
+    pub elapsed: f32,
+}
+
+fn drop_loot(
+    mut commands: Commands,
+    mut killed: MessageReader<EnemyKilledEvent>,
+    tables: Res<LootTables>,
+    mut pity: ResMut<LootPity>,
+    level_assets: Res<LevelAssets>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let mut rng = rand::rng();
+
+    for ev in killed.read() {
+        let Some(id) = ev.loot else { continue; };
+        let Some(table) = tables.0.get(&id) else {
+            warn!("Tabla de loot {:?} no registrada", id);
+            continue;
+        };
+
+        let mut drops = table.guaranteed.clone();
+        for _ in 0..table.rolls {
+            drops.extend(table.roll(&mut rng));
+        }
+
+        if let Some((pity_kind, after)) = table.pity {
+            let counter = pity.0.entry(id).or_default();
+            if drops.contains(&pity_kind) {
+                *counter = 0;
+            } else {
+                *counter += 1;
+                if *counter >= after {
+                    drops.push(pity_kind);
+                    *counter = 0;
+                    info!("Pity de {:?}: {:?} garantizado", id, pity_kind);
+                }
+            }
+        }
+
+        for kind in drops {
+            let pop = Vec3::new(
+                rng.random_range(-1.0..1.0_f32),
+                0.0,
+                rng.random_range(-1.0..1.0_f32),
+            ).normalize_or_zero() * 0.4 + Vec3::Y;
+            let position = ev.position + Vec3::Y * 1.0;
+            spawn_loot(&mut commands, &level_assets, &mut meshes, &mut materials, kind, position, pop * POP_SPEED);
+            info!("Loot: {:?} de {:?} ({:?})", kind, ev.enemy, ev.cause);
+        }
+    }
+}
+
+fn spawn_loot(
+    commands: &mut Commands,
+    level_assets: &LevelAssets,
+    meshes: &mut ResMut<Assets<Mesh>>,
+    materials: &mut ResMut<Assets<StandardMaterial>>,
+    kind: LootKind,
+    position: Vec3,
+    velocity: Vec3,
+) {
+    let entity = match kind {
+        // El reloj es el de siempre: se agarra con F y se guarda como carga
+        LootKind::AlarmClock => spawn_alarm_clock(commands, level_assets, position),
+        LootKind::Health | LootKind::DamageBoost => {
+            let (name, color, emissive) = match kind {
+                LootKind::Health => ("LootHealth", Color::srgb(0.3, 1.0, 0.4), LinearRgba::new(0.5, 3.0, 0.8, 1.0)),
+                _ => ("LootDamageBoost", Color::srgb(1.0, 0.3, 0.2), LinearRgba::new(3.0, 0.6, 0.3, 1.0)),
+            };
+            commands
+                .spawn((
+                    Name::new(name),
+                    LootPickup { kind },
+                    Mesh3d(meshes.add(Sphere { radius: 0.2 })),
+                    MeshMaterial3d(materials.add(StandardMaterial {
+                        base_color: color,
+                        emissive,
+                        ..default()
+                    })),
+                    Transform::from_translation(position),
+                    RigidBody::Dynamic,
+                    Collider::sphere(0.2),
+                ))
+                .id()
+        }
+    };
+    commands.entity(entity).insert((
+        LootLifetime { elapsed: 0.0 },
+        LinearVelocity(velocity),
+        DespawnOnExit(Screen::Gameplay),
+    ));
+}
+
+// -----------------------------------------------
+// RECOGER / CADUCAR
+// -----------------------------------------------
+
+fn collect_loot(
+    mut commands: Commands,
+    pickups: Query<(Entity, &LootPickup, &LootLifetime, &GlobalTransform)>,
+    player: Single<(Entity, &Transform, &mut Player)>,
+) {
+    let (player_entity, player_transform, mut player) = player.into_inner();
+
+    for (entity, pickup, lifetime, transform) in pickups.iter() {
+        if lifetime.elapsed < PICKUP_DELAY
+            || transform.translation().distance(player_transform.translation) > PICKUP_RANGE
+        {
+            continue;
+        }
+
+        match pickup.kind {
+            LootKind::Health => {
+                player.health = (player.health + HEALTH_AMOUNT).min(1.0);
+                info!("Vida recogida: {:.2}", player.health);
+            }
+            LootKind::DamageBoost => {
+                commands.entity(player_entity).insert(DamageBoost::new());
+                info!("DamageBoost recogido!");
+            }
+            // Los relojes se recogen agarrándolos (`alarm_clock`)
+            LootKind::AlarmClock => continue,
+        }
+        commands.entity(entity).despawn();
+    }
+}
+
+/// Un drop agarrado ya es del jugador: deja de caducar
+fn claim_held_loot(
+    mut commands: Commands,
+    mut drops: Query<(Entity, &mut Visibility), (With<LootLifetime>, Added<Held>)>,
+) {
+    for (entity, mut visibility) in drops.iter_mut() {
+        *visibility = Visibility::Inherited;
+        commands.entity(entity).remove::<LootLifetime>();
+    }
+}
+
+fn loot_lifetime(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut drops: Query<(Entity, &mut LootLifetime, &mut Visibility)>,
+) {
+    for (entity, mut lifetime, mut visibility) in drops.iter_mut() {
+        lifetime.elapsed += time.delta_secs();
+        let remaining = LOOT_LIFETIME - lifetime.elapsed;
+
+        if remaining <= 0.0 {
+            commands.entity(entity).despawn();
+        } else if remaining < LOOT_BLINK_TIME {
+            // Parpadea cada vez más rápido
+            let blink = (remaining * (12.0 - remaining * 2.0)).sin() > 0.0;
+            *visibility = if blink { Visibility::Inherited } else { Visibility::Hidden };
+        }
+    }
+}
@@ -25,9 +25,9 @@ use crate::{
         gameplay::{
             character_controller::CameraRotation,
             hammerhead::HammerheadAssets,
-            katana::{katana_animation, katana_setup, poor_setup_for_katana_animations},
             player::Player,
             events::SpawnAlarmClockEvent,
+            alarm_clock::AlarmClock,
         },
         set_cursor_grab,
     },
@@ -36,6 +36,24 @@ use crate::{
 mod character_controller;
 mod checkpoints;
 mod enemy;
+mod enemy_ai;
+mod enemy_archetype;
+mod enemy_death;
+mod flying_enemy;
+mod boss;
+mod health_bar;
+mod perception;
+mod charge_attack;
+mod combat_coordinator;
+mod crowd;
+mod ai_lod;
+mod arena;
+mod trigger;
+mod spawn_telegraph;
+mod poise;
+#[cfg(feature = "dev")]
+pub(crate) mod enemy_bench;
+mod ledge;
 mod hammerhead;
 mod katana;
 mod player;
@@ -50,6 +68,17 @@ mod cloud_goop;
 mod alarm_clock;
 mod events;
 mod particle_system;
+mod combo_system;
+mod hitstop;
+mod weapons;
+mod puppy;
+mod grab;
+mod loot;
+mod powerups;
+mod objective;
+mod survival;
+
+pub use survival::{GameMode, SurvivalRun};
 
 #[derive(Component)]
 struct Level;
@@ -79,12 +108,37 @@ pub(super) fn plugin(app: &mut App) {
         cloud_goop::CloudGoopPlugin,
         alarm_clock::AlarmClockPlugin,
         particle_system::ParticleSystemPlugin,
+        hitstop::HitstopPlugin,
+        combo_system::ComboSystemPlugin,
+        weapons::WeaponsPlugin,
+        puppy::PuppyPlugin,
+        grab::GrabPlugin,
+        loot::LootPlugin,
+        powerups::PowerUpsPlugin,
+        combat_coordinator::CombatCoordinatorPlugin,
+        crowd::CrowdPlugin,
+        enemy_archetype::plugin,
+    ));
+
+    app.add_plugins((
+        enemy_death::EnemyDeathPlugin,
+        health_bar::HealthBarPlugin,
+        flying_enemy::FlyingEnemyPlugin,
+        boss::BossPlugin,
+        objective::ObjectivePlugin,
+        ledge::LedgePlugin,
+        ai_lod::AiLodPlugin,
+        poise::PoisePlugin,
+        arena::ArenaPlugin,
+        trigger::TriggerPlugin,
+        spawn_telegraph::SpawnTelegraphPlugin,
+        survival::SurvivalPlugin,
     ));
 
     app.load_resource::<LevelAssets>();
     app.add_systems(
         OnEnter(Screen::Gameplay),
-        (setup_clock_spawn_resources, spawn_level, katana_setup).chain(),
+        (setup_clock_spawn_resources, spawn_level).chain(),
     );
     app.add_systems(
         OnExit(Screen::Gameplay),
@@ -127,10 +181,6 @@ pub(super) fn plugin(app: &mut App) {
     app.add_systems(
         Update,
         (
-            poor_setup_for_katana_animations,
-            katana_animation
-                .in_set(PausableSystems)
-                .run_if(in_state(Screen::Gameplay)),
             debug_spawn_clock
                 .run_if(in_state(Screen::Gameplay)),
             cache_clock_spawn_points.run_if(in_state(Screen::Gameplay)),
@@ -174,6 +224,8 @@ pub struct LevelAssets {
     #[dependency]
     katana_scene: Handle<Scene>,
     #[dependency]
+    puppy_scene: Handle<Scene>,
+    #[dependency]
     hammerhead: HammerheadAssets,
     #[dependency]
     alarm_clock_scene: Handle<Scene>,
@@ -204,6 +256,7 @@ impl FromWorld for LevelAssets {
             katana_idle: assets.load(GltfAssetLabel::Animation(0).from_asset("models/katana.glb")),
             katana_swing: assets.load(GltfAssetLabel::Animation(1).from_asset("models/katana.glb")),
             katana_scene: assets.load(GltfAssetLabel::Scene(0).from_asset("models/katana.glb")),
+            puppy_scene: assets.load(GltfAssetLabel::Scene(0).from_asset("models/puppy.glb")),
             alarm_clock_scene: assets.load(GltfAssetLabel::Scene(0).from_asset("models/alarm_clock.glb")),
             hammerhead: HammerheadAssets::load(assets),
             time_field_scene: assets.load(GltfAssetLabel::Scene(0).from_asset("models/time_field.glb")),
@@ -216,10 +269,15 @@ fn go_to_death_menu(
     mut next_menu: ResMut<NextState<Menu>>,
     mut paused: ResMut<NextState<Pause>>,
     player: Single<&Player>,
+    mode: Res<GameMode>,
 ) {
     if !player.is_alive() {
         commands.run_system_cached(spawn_background_overlay);
-        next_menu.set(Menu::Death);
+        // En supervivencia no se reaparece: se acaba la partida
+        next_menu.set(match *mode {
+            GameMode::Story => Menu::Death,
+            GameMode::Survival => Menu::SurvivalOver,
+        });
         paused.set(Pause(true));
     }
 }
@@ -234,8 +292,11 @@ fn spawn_level(
     commands.insert_resource(NavmeshDone(false));
     let camera = *camera;
 
-    let archipelago_options: ArchipelagoOptions<ThreeD> =
+    let mut archipelago_options: ArchipelagoOptions<ThreeD> =
         ArchipelagoOptions::from_agent_radius(0.5);
+    // Avoidance local entre agentes: que se abran en vez de apelotonarse
+    archipelago_options.agent_options.neighbourhood = 4.0;
+    archipelago_options.agent_options.avoidance_time_horizon = 1.5;
 
     let archipelago_id = commands.spawn(Archipelago3d::new(archipelago_options)).id();
 
@@ -342,6 +403,9 @@ struct ClockSpawnPoints(Vec<Vec3>);
 #[derive(Resource)]
 struct ClockSpawnTimer(Timer);
 
+/// Un empty no repone reloj si aún hay uno a esta distancia
+const CLOCK_RESPAWN_CLEARANCE: f32 = 3.0;
+
 fn generate_navmesh(
     mut generator: NavmeshGenerator,
     island: Query<&NavMeshHandle3d, With<Island>>,
@@ -403,12 +467,23 @@ fn spawn_clocks_from_empties(
     time: Res<Time>,
     mut timer: ResMut<ClockSpawnTimer>,
     points: Res<ClockSpawnPoints>,
+    clocks: Query<(&GlobalTransform, &AlarmClock)>,
 ) {
     if points.0.is_empty() { return; }
     timer.0.tick(time.delta());
     if !timer.0.just_finished() { return; }
+
+    let mut count = 0;
     for pos in points.0.iter().copied() {
+        // Solo repone el reloj si el de este empty ya se recogió
+        let occupied = clocks.iter().any(|(transform, clock)| {
+            !clock.is_thrown && transform.translation().distance(pos) < CLOCK_RESPAWN_CLEARANCE
+        });
+        if occupied { continue; }
         alarm_clock::spawn_alarm_clock(&mut commands, &level_assets, pos);
+        count += 1;
+    }
+    if count > 0 {
+        info!("Relojes spawneados desde {} empties", count);
     }
-    info!("Relojes spawneados desde {} empties", points.0.len());
 }
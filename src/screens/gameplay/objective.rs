@@ -0,0 +1,106 @@
+//! Objetivo del nivel
+//!
+//! Único sitio que decide cuándo se gana. Por defecto hay que romper todas
+//! las cápsulas; si el nivel tiene un jefe (un `EnemySpawn` cuyo tipo tiene
+//! sección `boss`), el objetivo pasa a ser derrotarlo. Al cumplirse se manda
+//! `LevelWonEvent` y se abre el menú de victoria.
+//!
+//! En supervivencia no hay objetivo: la partida acaba al morir.
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::menus::Menu;
+use crate::screens::Screen;
+use crate::screens::gameplay::{
+    boss::BossDefeatedEvent,
+    enemy_archetype::{EnemyArchetype, EnemyArchetypes},
+    enemy_spawn::EnemySpawn,
+    events::LevelWonEvent,
+    flower_capsule::CapsuleTracker,
+    survival::survival_mode,
+};
+
+pub struct ObjectivePlugin;
+
+impl Plugin for ObjectivePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<LevelWonEvent>();
+        app.init_resource::<LevelObjective>();
+        app.add_systems(OnEnter(Screen::Gameplay), reset_objective);
+        app.add_systems(
+            Update,
+            (detect_boss_level, track_bosses, check_objective)
+                .chain()
+                .run_if(in_state(Screen::Gameplay).and(not(survival_mode))),
+        );
+    }
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Deserialize)]
+pub enum ObjectiveGoal {
+    BreakAllCapsules,
+    DefeatBoss,
+}
+
+#[derive(Resource, Debug)]
+pub struct LevelObjective {
+    pub goal: ObjectiveGoal,
+    pub bosses_defeated: u32,
+    won: bool,
+}
+
+impl Default for LevelObjective {
+    fn default() -> Self {
+        Self {
+            goal: ObjectiveGoal::BreakAllCapsules,
+            bosses_defeated: 0,
+            won: false,
+        }
+    }
+}
+
+fn reset_objective(mut objective: ResMut<LevelObjective>) {
+    *objective = LevelObjective::default();
+}
+
+/// Si en la escena hay un spawn de jefe, el nivel es de jefe
+fn detect_boss_level(
+    spawns: Query<&EnemySpawn, Added<EnemySpawn>>,
+    archetypes: Res<EnemyArchetypes>,
+    archetype_assets: Res<Assets<EnemyArchetype>>,
+    mut objective: ResMut<LevelObjective>,
+) {
+    for spawn in &spawns {
+        let Ok((_, archetype)) = archetypes.get(&spawn.r#type, &archetype_assets) else { continue; };
+        if archetype.def.boss.is_some() && objective.goal != ObjectiveGoal::DefeatBoss {
+            objective.goal = ObjectiveGoal::DefeatBoss;
+            info!("Nivel con jefe: el objetivo es derrotar a '{}'", archetype.def.name);
+        }
+    }
+}
+
+fn track_bosses(mut defeated: MessageReader<BossDefeatedEvent>, mut objective: ResMut<LevelObjective>) {
+    objective.bosses_defeated += defeated.read().count() as u32;
+}
+
+fn check_objective(
+    tracker: Res<CapsuleTracker>,
+    mut objective: ResMut<LevelObjective>,
+    mut next_menu: ResMut<NextState<Menu>>,
+    mut won_writer: MessageWriter<LevelWonEvent>,
+) {
+    if objective.won {
+        return;
+    }
+    let done = match objective.goal {
+        ObjectiveGoal::BreakAllCapsules => tracker.all_broken(),
+        ObjectiveGoal::DefeatBoss => objective.bosses_defeated > 0,
+    };
+    if done {
+        objective.won = true;
+        info!("Objetivo cumplido: {:?}", objective.goal);
+        won_writer.write(LevelWonEvent { goal: objective.goal });
+        next_menu.set(Menu::Victory);
+    }
+}
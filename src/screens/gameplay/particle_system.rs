@@ -5,12 +5,18 @@
 //!
 //! - Nubes: chispas oscuras que emanan constantemente (detectadas por CloudGoopAnimated)
 //! - Enemigos: partículas idle + burst al recibir Knockback (detectados por Enemy component)
+//!
+//! Todas comparten la misma malla y unos pocos materiales, y hay un máximo de
+//! partículas vivas: las idle solo usan parte del presupuesto para que los
+//! bursts siempre tengan hueco, y los enemigos dormidos (`ai_lod`) no echan.
 
 use bevy::prelude::*;
 use rand::RngExt;
 use crate::screens::Screen;
+use crate::screens::gameplay::ai_lod::{AiLod, LodLevel};
 use crate::screens::gameplay::enemy::{Enemy, Knockback};
 use crate::screens::gameplay::cloud_goop::CloudGoopAnimated;
+use crate::screens::gameplay::alarm_clock::TimeDilation;
 
 pub struct ParticleSystemPlugin;
 
@@ -27,8 +33,15 @@ const ENEMY_IDLE_INTERVAL: f32   = 0.2;
 const ENEMY_IDLE_COUNT: usize    = 2;
 const ENEMY_BURST_COUNT: usize   = 14;
 
+/// Máximo de partículas oscuras vivas a la vez
+const MAX_PARTICLES: usize         = 600;
+/// Las idle (nubes y enemigos) no pasan de aquí
+const MAX_IDLE_PARTICLES: usize    = 400;
+
 impl Plugin for ParticleSystemPlugin {
     fn build(&self, app: &mut App) {
+        app.init_resource::<ParticleAssets>();
+        app.init_resource::<ParticleBudget>();
         app.add_systems(
             Update,
             (
@@ -66,6 +79,57 @@ pub struct DarkParticle {
     max_lifetime: f32,
 }
 
+// -----------------------------------------------
+// RECURSOS
+// -----------------------------------------------
+
+/// Malla y materiales compartidos por todas las partículas
+#[derive(Resource)]
+pub struct ParticleAssets {
+    mesh: Handle<Mesh>,
+    /// Un material por color oscuro, con el emisivo de nube
+    cloud: [Handle<StandardMaterial>; 3],
+    /// Lo mismo con el emisivo de enemigo
+    enemy: [Handle<StandardMaterial>; 3],
+    burst: Handle<StandardMaterial>,
+}
+
+impl FromWorld for ParticleAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mesh = world.resource_mut::<Assets<Mesh>>().add(Sphere { radius: 1.0 });
+        let mut materials = world.resource_mut::<Assets<StandardMaterial>>();
+        let mut material = |color: (f32, f32, f32), emissive: (f32, f32, f32)| {
+            materials.add(StandardMaterial {
+                base_color: Color::srgb(color.0, color.1, color.2),
+                emissive: LinearRgba::new(emissive.0, emissive.1, emissive.2, 1.0),
+                ..default()
+            })
+        };
+        let colors = [COLOR_BLACK, COLOR_WINE, COLOR_DARK_PURPLE];
+        Self {
+            mesh,
+            cloud: colors.map(|c| material(c, EMISSIVE_CLOUD)),
+            enemy: colors.map(|c| material(c, EMISSIVE_ENEMY)),
+            burst: material(COLOR_WINE, EMISSIVE_ENEMY),
+        }
+    }
+}
+
+/// Cuántas partículas hay vivas (lo cuenta `dark_particle_tick`)
+#[derive(Resource, Default)]
+pub struct ParticleBudget {
+    alive: usize,
+}
+
+impl ParticleBudget {
+    /// Cuántas de las `wanted` caben, y las apunta
+    fn take(&mut self, wanted: usize, limit: usize) -> usize {
+        let granted = wanted.min(limit.saturating_sub(self.alive));
+        self.alive += granted;
+        granted
+    }
+}
+
 // -----------------------------------------------
 // SETUP — añade emitters a nubes y enemigos
 // -----------------------------------------------
@@ -77,7 +141,7 @@ fn setup_cloud_emitters(
 ) {
     for entity in query.iter() {
         commands.entity(entity).insert(CloudEmitter { timer: 0.0 });
-        info!("CloudEmitter añadido a nube");
+        debug!("CloudEmitter añadido a nube");
     }
 }
 
@@ -91,7 +155,7 @@ fn setup_enemy_emitters(
             timer: 0.0,
             burst_done: false,
         });
-        info!("EnemyEmitter añadido a enemigo {:?}", entity);
+        debug!("EnemyEmitter añadido a enemigo {:?}", entity);
     }
 }
 
@@ -101,8 +165,8 @@ fn setup_enemy_emitters(
 
 fn cloud_particles(
     mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
+    assets: Res<ParticleAssets>,
+    mut budget: ResMut<ParticleBudget>,
     time: Res<Time>,
     mut emitters: Query<(&GlobalTransform, &mut CloudEmitter)>,
 ) {
@@ -112,14 +176,8 @@ fn cloud_particles(
         if emitter.timer > 0.0 { continue; }
         emitter.timer = CLOUD_SPAWN_INTERVAL;
 
-        spawn_dark_particles(
-            &mut commands,
-            &mut meshes,
-            &mut materials,
-            transform.translation(),
-            CLOUD_PARTICLE_COUNT,
-            ParticleStyle::Cloud,
-        );
+        let count = budget.take(CLOUD_PARTICLE_COUNT, MAX_IDLE_PARTICLES);
+        spawn_dark_particles(&mut commands, &assets, transform.translation(), count, ParticleStyle::Cloud);
     }
 }
 
@@ -129,44 +187,33 @@ fn cloud_particles(
 
 fn enemy_particles(
     mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
-    mut materials: ResMut<Assets<StandardMaterial>>,
+    assets: Res<ParticleAssets>,
+    mut budget: ResMut<ParticleBudget>,
     time: Res<Time>,
-    mut emitters: Query<(&GlobalTransform, &mut EnemyEmitter, Option<&Knockback>), With<Enemy>>,
+    mut emitters: Query<(&GlobalTransform, &mut EnemyEmitter, Option<&Knockback>, Option<&AiLod>), With<Enemy>>,
 ) {
     let dt = time.delta_secs();
-    for (transform, mut emitter, knockback) in emitters.iter_mut() {
+    for (transform, mut emitter, knockback, lod) in emitters.iter_mut() {
         let origin = transform.translation();
 
         // Burst UNA SOLA VEZ al inicio del knockback
         if knockback.is_some() && !emitter.burst_done {
             emitter.burst_done = true;
-            spawn_dark_particles(
-                &mut commands,
-                &mut meshes,
-                &mut materials,
-                origin,
-                ENEMY_BURST_COUNT,
-                ParticleStyle::EnemyBurst,
-            );
+            let count = budget.take(ENEMY_BURST_COUNT, MAX_PARTICLES);
+            spawn_dark_particles(&mut commands, &assets, origin, count, ParticleStyle::EnemyBurst);
         }
         if knockback.is_none() {
             emitter.burst_done = false;
         }
 
-        // Idle — emana siempre
+        // Idle — emana siempre, salvo dormido lejos del jugador
+        if lod.is_some_and(|lod| lod.level == LodLevel::Asleep) { continue; }
         emitter.timer -= dt;
         if emitter.timer > 0.0 { continue; }
         emitter.timer = ENEMY_IDLE_INTERVAL;
 
-        spawn_dark_particles(
-            &mut commands,
-            &mut meshes,
-            &mut materials,
-            origin,
-            ENEMY_IDLE_COUNT,
-            ParticleStyle::EnemyIdle,
-        );
+        let count = budget.take(ENEMY_IDLE_COUNT, MAX_IDLE_PARTICLES);
+        spawn_dark_particles(&mut commands, &assets, origin, count, ParticleStyle::EnemyIdle);
     }
 }
 
@@ -177,10 +224,13 @@ fn enemy_particles(
 fn dark_particle_tick(
     mut commands: Commands,
     time: Res<Time>,
-    mut particles: Query<(Entity, &mut Transform, &mut DarkParticle)>,
+    mut budget: ResMut<ParticleBudget>,
+    mut particles: Query<(Entity, &mut Transform, &mut DarkParticle, Option<&TimeDilation>)>,
 ) {
-    let dt = time.delta_secs();
-    for (entity, mut transform, mut particle) in particles.iter_mut() {
+    budget.alive = 0;
+    for (entity, mut transform, mut particle, dilation) in particles.iter_mut() {
+        // Dentro de un time field las partículas también se frenan
+        let dt = time.delta_secs() * dilation.map_or(1.0, |d| d.scale);
         particle.lifetime -= dt;
         particle.velocity += Vec3::NEG_Y * 2.5 * dt; // gravedad suave
         transform.translation += particle.velocity * dt;
@@ -191,6 +241,8 @@ fn dark_particle_tick(
 
         if particle.lifetime <= 0.0 {
             commands.entity(entity).despawn();
+        } else {
+            budget.alive += 1;
         }
     }
 }
@@ -207,40 +259,30 @@ enum ParticleStyle {
 
 fn spawn_dark_particles(
     commands: &mut Commands,
-    meshes: &mut ResMut<Assets<Mesh>>,
-    materials: &mut ResMut<Assets<StandardMaterial>>,
+    assets: &ParticleAssets,
     origin: Vec3,
     count: usize,
     style: ParticleStyle,
 ) {
+    if count == 0 { return; }
     let mut rng = rand::rng();
 
-    let (base_color, emissive, speed_min, speed_max, life_min, life_max, spread_y_min, spread_y_max) =
+    let (mat, speed_min, speed_max, life_min, life_max, spread_y_min, spread_y_max) =
         match style {
             ParticleStyle::Cloud => (
-                pick_dark_color(&mut rng),
-                EMISSIVE_CLOUD,
+                assets.cloud[pick_dark_color(&mut rng)].clone(),
                 0.8_f32, 2.5, 0.6, 1.5, -0.4_f32, 0.6,
             ),
             ParticleStyle::EnemyIdle => (
-                pick_dark_color(&mut rng),
-                EMISSIVE_ENEMY,
+                assets.enemy[pick_dark_color(&mut rng)].clone(),
                 0.3_f32, 1.2, 0.4, 0.9, 0.1_f32, 0.9,
             ),
             ParticleStyle::EnemyBurst => (
-                COLOR_WINE,
-                EMISSIVE_ENEMY,
+                assets.burst.clone(),
                 2.0_f32, 5.5, 0.5, 1.2, 0.3_f32, 2.2,
             ),
         };
 
-    let mesh = meshes.add(Sphere { radius: 1.0 });
-    let mat = materials.add(StandardMaterial {
-        base_color: Color::srgb(base_color.0, base_color.1, base_color.2),
-        emissive: LinearRgba::new(emissive.0, emissive.1, emissive.2, 1.0),
-        ..default()
-    });
-
     for _ in 0..count {
         let dir = Vec3::new(
             rng.random_range(-1.0..1.0_f32),
@@ -260,17 +302,25 @@ fn spawn_dark_particles(
                 lifetime,
                 max_lifetime: lifetime,
             },
-            Mesh3d(mesh.clone()),
+            Mesh3d(assets.mesh.clone()),
             MeshMaterial3d(mat.clone()),
             Transform::from_translation(origin).with_scale(Vec3::splat(scale)),
         ));
     }
 }
 
-fn pick_dark_color(rng: &mut impl rand::Rng) -> (f32, f32, f32) {
-    match rng.random_range(0..3_u32) {
-        0 => COLOR_BLACK,
-        1 => COLOR_WINE,
-        _ => COLOR_DARK_PURPLE,
-    }
+/// Índice en los materiales: negro, vino o morado
+fn pick_dark_color(rng: &mut impl rand::Rng) -> usize {
+    rng.random_range(0..3)
+}
+
+/// Explosión de partículas oscuras cuando un enemigo se deshace al morir
+pub fn spawn_death_burst(
+    commands: &mut Commands,
+    assets: &ParticleAssets,
+    budget: &mut ParticleBudget,
+    origin: Vec3,
+) {
+    let count = budget.take(ENEMY_BURST_COUNT * 2, MAX_PARTICLES);
+    spawn_dark_particles(commands, assets, origin, count, ParticleStyle::EnemyBurst);
 }
@@ -0,0 +1,235 @@
+//! Percepción de los enemigos: vista, oído y memoria
+//!
+//! - Vista: cono de visión + raycast de línea de visión con `SpatialQuery`.
+//!   Muy de cerca se nota al jugador aunque esté detrás.
+//! - Oído: el jugador hace `Noise` al andar, al hacer dash y al pelear. Los
+//!   enemigos dentro del radio lo oyen a través de las paredes.
+//! - Memoria: se recuerda la última posición conocida un rato; el cerebro
+//!   va a buscar ahí cuando lo pierde de vista.
+//! - Un enemigo que ve al jugador avisa a los de alrededor con otro `Noise`.
+
+use avian3d::prelude::*;
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::screens::gameplay::{
+    ai_lod::{self, AiLod},
+    character_controller::{AttackAction, MovementAction},
+    enemy::Enemy,
+    enemy_ai::EnemyBehaviour,
+    hitstop::MeleeImpact,
+    player::Player,
+};
+
+/// Cada cuánto suena un paso al correr
+const FOOTSTEP_INTERVAL: f32 = 0.4;
+/// Por debajo de esta velocidad el jugador no hace ruido al moverse
+const FOOTSTEP_MIN_SPEED: f32 = 1.5;
+const FOOTSTEP_RADIUS: f32 = 6.0;
+const DASH_RADIUS: f32 = 12.0;
+const COMBAT_RADIUS: f32 = 18.0;
+/// Altura del punto del jugador al que miran los enemigos
+pub const PLAYER_CHEST: f32 = 1.2;
+
+// -----------------------------------------------
+// CONFIGURACIÓN / ESTADO
+// -----------------------------------------------
+
+#[derive(Clone, Debug, Reflect, Deserialize)]
+pub struct PerceptionSettings {
+    pub sight_range: f32,
+    /// Ángulo total del cono de visión
+    pub fov_degrees: f32,
+    /// A esta distancia se nota al jugador aunque esté detrás
+    pub close_range: f32,
+    pub eye_height: f32,
+    /// Multiplica el radio de los ruidos que oye
+    pub hearing: f32,
+    /// Segundos que recuerda dónde vio al jugador por última vez
+    pub memory_time: f32,
+    /// Radio en el que avisa a otros enemigos al verlo
+    pub ally_alert_radius: f32,
+}
+
+impl Default for PerceptionSettings {
+    fn default() -> Self {
+        Self {
+            sight_range: 20.0,
+            fov_degrees: 120.0,
+            close_range: 2.5,
+            eye_height: 2.0,
+            hearing: 1.0,
+            memory_time: 6.0,
+            ally_alert_radius: 12.0,
+        }
+    }
+}
+
+/// Lo que el enemigo sabe del jugador ahora mismo
+#[derive(Component, Default, Debug)]
+pub struct Perception {
+    pub sees_player: bool,
+    /// Oyó algo este frame
+    pub heard: bool,
+    pub last_known: Option<Vec3>,
+    pub time_since_seen: f32,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum NoiseKind {
+    Footstep,
+    Dash,
+    Combat,
+    /// Un enemigo avisando a los demás
+    Ally,
+}
+
+/// Algo que los enemigos pueden oír
+#[derive(Message, Clone, Copy, Debug)]
+pub struct Noise {
+    pub kind: NoiseKind,
+    /// Desde dónde suena
+    pub source: Vec3,
+    /// Dónde hay que ir a mirar (para un aviso, dónde está el jugador)
+    pub target: Vec3,
+    pub radius: f32,
+    /// Quién lo hizo, para no oírse a sí mismo
+    pub emitter: Option<Entity>,
+}
+
+impl Noise {
+    fn from_player(kind: NoiseKind, position: Vec3, radius: f32) -> Self {
+        Self { kind, source: position, target: position, radius, emitter: None }
+    }
+}
+
+// -----------------------------------------------
+// RUIDOS DEL JUGADOR
+// -----------------------------------------------
+
+pub(super) fn player_noise(
+    time: Res<Time>,
+    mut footstep_timer: Local<f32>,
+    mut movement: MessageReader<MovementAction>,
+    mut attacks: MessageReader<AttackAction>,
+    mut impacts: MessageReader<MeleeImpact>,
+    player: Single<(&Transform, &LinearVelocity), With<Player>>,
+    mut noise_writer: MessageWriter<Noise>,
+) {
+    let (transform, velocity) = *player;
+    let position = transform.translation;
+
+    // Pasos
+    if velocity.xz().length() >= FOOTSTEP_MIN_SPEED {
+        *footstep_timer -= time.delta_secs();
+        if *footstep_timer <= 0.0 {
+            *footstep_timer = FOOTSTEP_INTERVAL;
+            noise_writer.write(Noise::from_player(NoiseKind::Footstep, position, FOOTSTEP_RADIUS));
+        }
+    } else {
+        *footstep_timer = 0.0;
+    }
+
+    if movement.read().any(|m| matches!(m, MovementAction::Dash(_))) {
+        noise_writer.write(Noise::from_player(NoiseKind::Dash, position, DASH_RADIUS));
+    }
+
+    // Golpear al aire ya hace ruido; conectar, más
+    let swung = attacks.read().count() > 0;
+    let connected = impacts.read().count() > 0;
+    if swung || connected {
+        let radius = if connected { COMBAT_RADIUS } else { COMBAT_RADIUS * 0.5 };
+        noise_writer.write(Noise::from_player(NoiseKind::Combat, position, radius));
+    }
+}
+
+// -----------------------------------------------
+// VISTA
+// -----------------------------------------------
+
+pub(super) fn update_sight(
+    time: Res<Time>,
+    mut enemies: Query<(Entity, &Transform, &EnemyBehaviour, &mut Perception, Option<&AiLod>)>,
+    player: Single<(Entity, &Transform, &Player)>,
+    enemy_bodies: Query<(), With<Enemy>>,
+    colliders: Query<&ColliderOf>,
+    spatial_query: SpatialQuery,
+) {
+    let (player_entity, player_transform, player) = *player;
+    let target = player_transform.translation + Vec3::Y * PLAYER_CHEST;
+
+    for (entity, transform, behaviour, mut perception, lod) in enemies.iter_mut() {
+        let Some(dt) = ai_lod::think_dt(lod, time.delta_secs()) else { continue; };
+        let settings = &behaviour.perception;
+        let eye = transform.translation + Vec3::Y * settings.eye_height;
+        let to_player = target - eye;
+        let distance = to_player.length();
+
+        let in_cone = distance <= settings.close_range
+            || (distance <= settings.sight_range
+                && transform.forward().angle_between(to_player.with_y(0.0)).to_degrees()
+                    <= settings.fov_degrees * 0.5);
+
+        // Línea de visión: ni otros enemigos ni el propio jugador tapan
+        let visible = player.is_alive()
+            && in_cone
+            && Dir3::new(to_player).is_ok_and(|dir| {
+                spatial_query
+                    .cast_ray_predicate(eye, dir, distance, true, &SpatialQueryFilter::default(), &|hit| {
+                        let body = colliders.get(hit).map_or(hit, |c| c.body);
+                        body != entity && body != player_entity && !enemy_bodies.contains(body)
+                    })
+                    .is_none()
+            });
+
+        perception.sees_player = visible;
+        if visible {
+            perception.last_known = Some(player_transform.translation);
+            perception.time_since_seen = 0.0;
+        } else {
+            perception.time_since_seen += dt;
+            if perception.time_since_seen > settings.memory_time {
+                perception.last_known = None;
+            }
+        }
+    }
+}
+
+// -----------------------------------------------
+// OÍDO
+// -----------------------------------------------
+
+pub(super) fn update_hearing(
+    mut noises: MessageReader<Noise>,
+    mut enemies: Query<(Entity, &Transform, &EnemyBehaviour, &mut Perception)>,
+) {
+    // `heard` lo limpia el cerebro cuando lo ha tenido en cuenta: con el LOD
+    // de la IA puede que no piense este frame
+    for noise in noises.read() {
+        for (entity, transform, behaviour, mut perception) in enemies.iter_mut() {
+            if noise.emitter == Some(entity) || perception.sees_player {
+                continue;
+            }
+            let radius = noise.radius * behaviour.perception.hearing;
+            if transform.translation.distance(noise.source) > radius {
+                continue;
+            }
+            perception.heard = true;
+            perception.last_known = Some(noise.target);
+            // Oírlo refresca la memoria igual que verlo
+            perception.time_since_seen = 0.0;
+            debug!("Enemy {:?} oyó {:?}", entity, noise.kind);
+        }
+    }
+}
+
+/// Aviso a los aliados cuando un enemigo ve al jugador
+pub fn ally_alert(entity: Entity, position: Vec3, player_position: Vec3, radius: f32) -> Noise {
+    Noise {
+        kind: NoiseKind::Ally,
+        source: position,
+        target: player_position,
+        radius,
+        emitter: Some(entity),
+    }
+}
@@ -4,6 +4,8 @@ use avian3d::prelude::*;
 use bevy::prelude::*;
 
 use crate::screens::gameplay::character_controller::CharacterControllerBundle;
+use crate::screens::gameplay::alarm_clock::ClockCharges;
+use crate::screens::gameplay::weapons::WeaponInventory;
 
 #[derive(Component, Debug, Clone, Copy, PartialEq, Reflect)]
 #[reflect(Component)]
@@ -46,6 +48,8 @@ pub fn spawn_player(commands: &mut Commands, camera: Entity) -> Entity {
             GravityScale(1.5),
             Transform::from_xyz(0.0, 0.9, 2.0),
             Player::default(),
+            WeaponInventory::default(),
+            ClockCharges::default(),
             TransformInterpolation,
             Children::spawn_one((player_collider, Transform::from_xyz(0., 0.9, 0.))),
         ))
@@ -0,0 +1,227 @@
+//! Poise y stagger
+//!
+//! Cada tipo de enemigo tiene una reserva de poise (`poise` en su
+//! `.enemy.ron`). Los golpes la gastan según el arma (`WeaponDef::poise_damage`;
+//! la patada poco) y, mientras aguante, el enemigo encaja el golpe sin
+//! inmutarse. Solo cuando se rompe sale despedido y queda aturdido
+//! (`EnemyState::Stunned`); durante ese stagger otro golpe no lo vuelve a
+//! tumbar, y al levantarse la poise vuelve entera. Si no, se regenera sola un
+//! rato después del último golpe.
+//!
+//! Algunos ataques tienen hyper-armor (`hyper_armor` en la embestida del
+//! hammerhead): mientras embiste, los golpes hacen daño pero no gastan poise.
+//!
+//! La rotura se ve: un anillo amarillo en el suelo alrededor del enemigo (que
+//! se abre y se aplana), un hitstop un poco más largo y la barrita de poise
+//! bajo la vida vacía.
+
+use bevy::prelude::*;
+use serde::Deserialize;
+
+use crate::screens::Screen;
+use crate::screens::gameplay::{
+    alarm_clock::TimeDilation,
+    enemy_ai::{EnemyBrain, EnemyState},
+};
+
+pub struct PoisePlugin;
+
+const RING_TIME: f32 = 0.45;
+const RING_RADIUS: f32 = 2.2;
+const RING_COLOR: (f32, f32, f32) = (1.0, 0.8, 0.15);
+
+impl Plugin for PoisePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<PoiseBroken>();
+        app.init_resource::<BreakRingAssets>();
+        app.add_systems(
+            Update,
+            (regen_poise, spawn_break_rings, break_ring_tick)
+                .chain()
+                .run_if(in_state(Screen::Gameplay)),
+        );
+    }
+}
+
+/// Poise de un tipo de enemigo (sección `poise` del `.enemy.ron`)
+#[derive(Deserialize, Clone, Debug)]
+pub struct PoiseSettings {
+    pub max: f32,
+    /// Por segundo, una vez pasado `regen_delay`
+    pub regen_rate: f32,
+    /// Segundos sin recibir golpes antes de empezar a recuperarse
+    pub regen_delay: f32,
+}
+
+impl Default for PoiseSettings {
+    /// Sin poise: cualquier golpe lo tumba
+    fn default() -> Self {
+        Self { max: 0.0, regen_rate: 0.0, regen_delay: 0.0 }
+    }
+}
+
+#[derive(Component, Debug)]
+pub struct Poise {
+    pub settings: PoiseSettings,
+    pub current: f32,
+    /// Rota y todavía en el stagger
+    pub broken: bool,
+    /// El cerebro ya ha pasado a `Stunned` por la rotura
+    staggering: bool,
+    regen_cooldown: f32,
+}
+
+impl Poise {
+    pub fn new(settings: PoiseSettings) -> Self {
+        Self {
+            current: settings.max,
+            settings,
+            broken: false,
+            staggering: false,
+            regen_cooldown: 0.0,
+        }
+    }
+
+    pub fn fraction(&self) -> f32 {
+        if self.settings.max <= 0.0 {
+            return 0.0;
+        }
+        (self.current / self.settings.max).clamp(0.0, 1.0)
+    }
+
+    /// Gasta poise con un golpe. Devuelve `true` si se acaba de romper.
+    pub fn hit(&mut self, amount: f32) -> bool {
+        if self.broken {
+            return false;
+        }
+        self.regen_cooldown = self.settings.regen_delay;
+        self.current -= amount;
+        if self.current <= 0.0 {
+            self.current = 0.0;
+            self.broken = true;
+        }
+        self.broken
+    }
+}
+
+/// Se rompió la poise de un enemigo
+#[derive(Message, Clone, Copy, Debug)]
+pub struct PoiseBroken {
+    pub enemy: Entity,
+    pub position: Vec3,
+}
+
+fn regen_poise(time: Res<Time>, mut enemies: Query<(&EnemyBrain, &mut Poise, Option<&TimeDilation>)>) {
+    for (brain, mut poise, dilation) in enemies.iter_mut() {
+        if poise.broken {
+            // Se levanta del stagger con la poise llena
+            if brain.state == EnemyState::Stunned {
+                poise.staggering = true;
+            } else if poise.staggering {
+                poise.broken = false;
+                poise.staggering = false;
+                poise.current = poise.settings.max;
+            }
+            continue;
+        }
+        let dt = time.delta_secs() * dilation.map_or(1.0, |d| d.scale);
+        poise.regen_cooldown -= dt;
+        if poise.regen_cooldown <= 0.0 {
+            poise.current = (poise.current + poise.settings.regen_rate * dt).min(poise.settings.max);
+        }
+    }
+}
+
+/// Anillo que se abre en el suelo al romper la poise
+#[derive(Component)]
+struct BreakRing {
+    timer: Timer,
+}
+
+/// Malla y material compartidos por todos los anillos
+#[derive(Resource)]
+struct BreakRingAssets {
+    mesh: Handle<Mesh>,
+    material: Handle<StandardMaterial>,
+}
+
+impl FromWorld for BreakRingAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mesh = world.resource_mut::<Assets<Mesh>>().add(Torus::new(0.85, 1.0));
+        let material = world.resource_mut::<Assets<StandardMaterial>>().add(StandardMaterial {
+            base_color: Color::srgba(RING_COLOR.0, RING_COLOR.1, RING_COLOR.2, 0.9),
+            emissive: LinearRgba::new(RING_COLOR.0 * 4.0, RING_COLOR.1 * 4.0, RING_COLOR.2 * 4.0, 1.0),
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
+            ..default()
+        });
+        Self { mesh, material }
+    }
+}
+
+fn spawn_break_rings(
+    mut commands: Commands,
+    mut broken: MessageReader<PoiseBroken>,
+    assets: Res<BreakRingAssets>,
+) {
+    for event in broken.read() {
+        commands.spawn((
+            Name::new("PoiseBreakRing"),
+            BreakRing { timer: Timer::from_seconds(RING_TIME, TimerMode::Once) },
+            Mesh3d(assets.mesh.clone()),
+            MeshMaterial3d(assets.material.clone()),
+            Transform::from_translation(event.position + Vec3::Y * 0.1).with_scale(Vec3::splat(0.3)),
+            DespawnOnExit(Screen::Gameplay),
+        ));
+    }
+}
+
+fn break_ring_tick(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut rings: Query<(Entity, &mut BreakRing, &mut Transform)>,
+) {
+    for (entity, mut ring, mut transform) in rings.iter_mut() {
+        ring.timer.tick(time.delta());
+        let k = ring.timer.fraction();
+        // El material es compartido: en vez de desvanecerse, se aplana
+        let radius = RING_RADIUS * (0.3 + 0.7 * k);
+        transform.scale = Vec3::new(radius, 1.0 - k, radius);
+        if ring.timer.is_finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::screens::gameplay::katana::KATANA_POISE;
+
+    fn hammerhead() -> Poise {
+        Poise::new(PoiseSettings { max: 15.0, regen_rate: 10.0, regen_delay: 1.5 })
+    }
+
+    #[test]
+    fn one_katana_hit_does_not_break_a_hammerhead() {
+        let mut poise = hammerhead();
+        assert!(!poise.hit(KATANA_POISE));
+        assert!(!poise.broken);
+    }
+
+    #[test]
+    fn third_katana_hit_breaks_a_hammerhead() {
+        let mut poise = hammerhead();
+        assert!(!poise.hit(KATANA_POISE));
+        assert!(!poise.hit(KATANA_POISE));
+        assert!(poise.hit(KATANA_POISE));
+        assert_eq!(poise.current, 0.0);
+    }
+
+    #[test]
+    fn broken_poise_ignores_more_hits() {
+        let mut poise = hammerhead();
+        assert!(poise.hit(100.0));
+        assert!(!poise.hit(KATANA_POISE));
+    }
+}
@@ -1,21 +1,60 @@
+//! Powerups temporales que el jugador lleva encima
+//!
+//! De momento solo `DamageBoost`: multiplica el daño de los golpes hasta que
+//! se acaba el tiempo. Se consiguen como loot de los enemigos.
+
 use bevy::prelude::*;
 
-pub struct PowerUpsPlugin {
+use crate::PausableSystems;
+use crate::screens::Screen;
 
-}
+pub struct PowerUpsPlugin;
 
-impl Plugin for PowerUpsPlugin {
+pub const DAMAGE_BOOST_MULTIPLIER: u32 = 2;
+pub const DAMAGE_BOOST_DURATION: f32 = 8.0;
 
+impl Plugin for PowerUpsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            Update,
+            temporary_modifiers
+                .in_set(PausableSystems)
+                .run_if(in_state(Screen::Gameplay)),
+        );
+    }
 }
 
-fn gain_more_damage () {
-
+/// Golpes más fuertes durante un rato
+#[derive(Component)]
+pub struct DamageBoost {
+    pub multiplier: u32,
+    pub remaining: f32,
 }
 
-fn temporary_modifiers() {
-
+impl DamageBoost {
+    pub fn new() -> Self {
+        Self {
+            multiplier: DAMAGE_BOOST_MULTIPLIER,
+            remaining: DAMAGE_BOOST_DURATION,
+        }
+    }
 }
 
-fn butterfly_healing() {
+/// Daño final de un golpe con los modificadores activos
+pub fn gain_more_damage(damage: u32, boost: Option<&DamageBoost>) -> u32 {
+    damage * boost.map_or(1, |b| b.multiplier)
+}
 
+fn temporary_modifiers(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut boosts: Query<(Entity, &mut DamageBoost)>,
+) {
+    for (entity, mut boost) in boosts.iter_mut() {
+        boost.remaining -= time.delta_secs();
+        if boost.remaining <= 0.0 {
+            commands.entity(entity).remove::<DamageBoost>();
+            info!("DamageBoost terminado");
+        }
+    }
 }
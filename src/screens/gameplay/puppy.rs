@@ -0,0 +1,168 @@
+//! Puppy — el perrito que se convierte en la katana
+//!
+//! Flujo:
+//! Puppy flota en el nivel → jugador se acerca → Puppy salta a la cámara,
+//! gira y se encoge → estallido de estrellas → katana equipada
+
+use bevy::prelude::*;
+
+use crate::audio::sound_effect;
+use crate::screens::Screen;
+use crate::screens::gameplay::{
+    Level, LevelAssets, spawn_level,
+    alarm_clock::spawn_stars,
+    player::Player,
+    weapons::{WeaponId, WeaponInventory},
+};
+
+pub struct PuppyPlugin;
+
+const PICKUP_RANGE: f32 = 1.8;
+/// Si el nivel no tiene un empty "PuppySpawn", Puppy aparece aquí
+const FALLBACK_POSITION: Vec3 = Vec3::new(0.0, 1.2, -4.0);
+/// Duración de cada fase de la transformación (segundos)
+const JUMP_TIME: f32 = 0.35;
+const SPIN_TIME: f32 = 0.6;
+const SHRINK_TIME: f32 = 0.25;
+
+impl Plugin for PuppyPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(Screen::Gameplay), spawn_puppy.after(spawn_level));
+        app.add_systems(
+            Update,
+            (
+                place_puppy_at_empty,
+                puppy_idle,
+                collect_puppy,
+                puppy_transformation,
+            )
+                .chain()
+                .in_set(crate::PausableSystems)
+                .run_if(in_state(Screen::Gameplay)),
+        );
+    }
+}
+
+// -----------------------------------------------
+// COMPONENTES
+// -----------------------------------------------
+
+/// Puppy esperando en el mundo
+#[derive(Component)]
+pub struct PuppyPickup {
+    base_position: Vec3,
+}
+
+/// Puppy pegado a la cámara transformándose en katana
+#[derive(Component)]
+pub struct PuppyTransformation {
+    elapsed: f32,
+}
+
+// -----------------------------------------------
+// SPAWN
+// -----------------------------------------------
+
+fn spawn_puppy(
+    mut commands: Commands,
+    level_assets: Res<LevelAssets>,
+    level: Single<Entity, With<Level>>,
+) {
+    commands.entity(*level).with_child((
+        Name::new("Puppy"),
+        PuppyPickup { base_position: FALLBACK_POSITION },
+        SceneRoot(level_assets.puppy_scene.clone()),
+        Transform::from_translation(FALLBACK_POSITION).with_scale(Vec3::splat(0.5)),
+    ));
+}
+
+fn place_puppy_at_empty(
+    named: Query<(&Name, &GlobalTransform), Added<GlobalTransform>>,
+    mut puppies: Query<&mut PuppyPickup>,
+) {
+    for (name, transform) in named.iter() {
+        if name.as_str() != "PuppySpawn" {
+            continue;
+        }
+        for mut puppy in puppies.iter_mut() {
+            puppy.base_position = transform.translation();
+            info!("Puppy colocado en {:?}", puppy.base_position);
+        }
+    }
+}
+
+// -----------------------------------------------
+// RECOGER
+// -----------------------------------------------
+
+fn puppy_idle(time: Res<Time>, mut puppies: Query<(&PuppyPickup, &mut Transform)>) {
+    let t = time.elapsed_secs();
+    for (puppy, mut transform) in puppies.iter_mut() {
+        transform.translation = puppy.base_position + Vec3::Y * (t * 2.0).sin() * 0.15;
+        transform.rotation = Quat::from_rotation_y(t * 0.8);
+    }
+}
+
+fn collect_puppy(
+    mut commands: Commands,
+    puppies: Query<(Entity, &Transform), With<PuppyPickup>>,
+    player: Single<&Transform, With<Player>>,
+    camera: Single<Entity, With<Camera3d>>,
+    level_assets: Res<LevelAssets>,
+    level: Single<Entity, With<Level>>,
+) {
+    for (entity, transform) in puppies.iter() {
+        if transform.translation.distance(player.translation) > PICKUP_RANGE {
+            continue;
+        }
+
+        commands.entity(entity).despawn();
+        commands.spawn((
+            Name::new("PuppyTransformation"),
+            PuppyTransformation { elapsed: 0.0 },
+            SceneRoot(level_assets.puppy_scene.clone()),
+            Transform::from_xyz(0.0, -0.6, -1.6).with_scale(Vec3::splat(0.3)),
+            ChildOf(*camera),
+        ));
+        commands.entity(*level).with_child(sound_effect(level_assets.whoosh1.clone(), ()));
+        info!("Puppy recogido!");
+    }
+}
+
+// -----------------------------------------------
+// TRANSFORMACIÓN
+// -----------------------------------------------
+
+fn puppy_transformation(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut transformations: Query<(Entity, &GlobalTransform, &mut Transform, &mut PuppyTransformation)>,
+    mut inventory: Single<&mut WeaponInventory, With<Player>>,
+) {
+    for (entity, global, mut transform, mut puppy) in transformations.iter_mut() {
+        puppy.elapsed += time.delta_secs();
+        let t = puppy.elapsed;
+
+        if t < JUMP_TIME {
+            // Salta hacia la cámara
+            let k = t / JUMP_TIME;
+            transform.translation = Vec3::new(0.0, -0.6 + 0.4 * k, -1.6 + 0.4 * k);
+            transform.scale = Vec3::splat(0.3 + 0.2 * k);
+        } else if t < JUMP_TIME + SPIN_TIME {
+            // Gira cada vez más rápido
+            let k = (t - JUMP_TIME) / SPIN_TIME;
+            transform.rotate_local_y(time.delta_secs() * (4.0 + 26.0 * k));
+        } else if t < JUMP_TIME + SPIN_TIME + SHRINK_TIME {
+            let k = (t - JUMP_TIME - SPIN_TIME) / SHRINK_TIME;
+            transform.rotate_local_y(time.delta_secs() * 30.0);
+            transform.scale = Vec3::splat(0.5 * (1.0 - k));
+        } else {
+            spawn_stars(&mut commands, &mut meshes, &mut materials, global.translation());
+            commands.entity(entity).despawn();
+            inventory.give(WeaponId::Katana);
+            info!("Puppy se transformó en katana!");
+        }
+    }
+}
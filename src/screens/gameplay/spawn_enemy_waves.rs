@@ -1,141 +1,727 @@
-use bevy::prelude::*;
+//! Director de oleadas
+//!
+//! Las oleadas de cada zona se definen en `assets/waves/*.waves.ron`: qué
+//! tipos de enemigo salen, cuántos, con qué retraso, y cuándo se da la
+//! oleada por superada (`ClearCondition`). La zona de cada `EnemySpawn` de
+//! Blender dice de dónde salen:
+//!
+//! - Con `wave: 0` el empty es un punto de salida de la zona: los enemigos
+//!   del archivo se reparten entre ellos por turnos, y su `delay` se suma al
+//!   del grupo.
+//! - Con `wave: N` es un enemigo colocado a mano: sale uno de su `type` en la
+//!   oleada N de su zona, `delay` segundos después de empezar.
+//!
+//! Una zona con `endless` no se acaba nunca: pasadas las oleadas del archivo
+//! genera otras, cada vez con más enemigos y más seguidos (el modo
+//! supervivencia). Con `points` usa los empties de otra zona, para jugarla
+//! en la geometría de una arena que ya existe.
+//!
+//! Solo hay una zona en marcha a la vez. Las que tienen `autostart` empiezan
+//! solas en el orden del archivo en cuanto hay navmesh; el resto las arranca
+//! otro sistema con `WaveDirector::start_zone`. Cada oleada avisa con
+//! `WaveStartedEvent` y `WaveClearedEvent`, y la zona con
+//! `ZoneCompletedEvent` al superar la última.
+//!
+//! Los enemigos no salen directamente: pasan por `spawn_telegraph`, que
+//! valida el sitio y los anuncia con una burbuja. Mientras tanto la burbuja
+//! ya cuenta como un enemigo vivo de la oleada.
 
-use crate::screens::gameplay::enemy_spawn::EnemySpawn;
-use crate::screens::gameplay::enemy::EnemySpawnCmd;
-use crate::screens::gameplay::enemy_spawn::SpawnConsumed;
+use std::collections::VecDeque;
+use std::fmt;
 
+use bevy::{
+    asset::{AssetLoader, LoadContext, io::Reader},
+    platform::collections::HashMap,
+    prelude::*,
+};
+use rand::RngExt;
+use serde::Deserialize;
+
+use crate::PausableSystems;
+use crate::asset_tracking::LoadResource;
 use crate::screens::Screen;
 use crate::screens::gameplay::{
-    enemy::spawn_enemy,
-    LevelAssets,
-    NavmeshArchipelagoHolder,
+    NavmeshDone,
+    boss::BossDefeatedEvent,
+    enemy::Enemy,
+    enemy_archetype::{EnemyArchetype, EnemyArchetypeHandle, EnemyArchetypes},
+    enemy_spawn::EnemySpawn,
+    events::{WaveClearedEvent, WaveStartedEvent, ZoneCompletedEvent},
+    flower_capsule::CapsuleTracker,
+    objective::ObjectiveGoal,
+    spawn_telegraph::{SpawnTelegraph, TelegraphedSpawnCmd},
 };
 
-/// ===============================
-/// RESOURCE: CONTROL DE OLEADAS
-/// ===============================
+/// Archivo de oleadas del nivel
+const WAVES_FILE: &str = "waves/demo.waves.ron";
+
+pub struct WaveSpawnPlugin;
+
+impl Plugin for WaveSpawnPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<EnemySpawn>();
+        app.init_asset::<WaveTable>();
+        app.init_asset_loader::<WaveTableLoader>();
+        app.load_resource::<WaveTables>();
+        app.add_message::<WaveStartedEvent>();
+        app.add_message::<WaveClearedEvent>();
+        app.add_message::<ZoneCompletedEvent>();
+        app.init_resource::<WaveDirector>();
+
+        app.add_systems(OnEnter(Screen::Gameplay), reset_director);
+        app.add_systems(
+            Update,
+            (start_zones, tick_waves, check_wave_clear)
+                .chain()
+                .in_set(PausableSystems)
+                .run_if(in_state(Screen::Gameplay)),
+        );
+    }
+}
+
+// -----------------------------------------------
+// DEFINICIÓN (lo que hay en el archivo)
+// -----------------------------------------------
+
+#[derive(Asset, TypePath, Deserialize, Clone, Debug, Default)]
+pub struct WaveTable {
+    pub zones: Vec<ZoneDef>,
+}
+
+impl WaveTable {
+    pub fn zone(&self, name: &str) -> Option<&ZoneDef> {
+        self.zones.iter().find(|zone| zone.name == name)
+    }
+}
 
-#[derive(Resource)]
-pub struct WaveManager {
-    pub timer: Timer,
-    pub current_wave: i32,
-    pub current_zone: String,
+#[derive(Deserialize, Clone, Debug)]
+pub struct ZoneDef {
+    /// La `zone` de los `EnemySpawn` de Blender
+    pub name: String,
+    /// Empieza sola; si no, la arranca otro sistema (una arena, por ejemplo)
+    #[serde(default)]
+    pub autostart: bool,
+    /// Segundos de respiro entre oleadas
+    #[serde(default)]
+    pub rest: f32,
+    /// Zona de Blender cuyos empties usa (por defecto, la suya)
+    #[serde(default)]
+    pub points: Option<String>,
+    #[serde(default)]
+    pub waves: Vec<WaveDef>,
+    /// Oleadas generadas sin fin tras las del archivo
+    #[serde(default)]
+    pub endless: Option<EndlessWaves>,
 }
 
-impl Default for WaveManager {
-    fn default() -> Self {
+impl ZoneDef {
+    /// Zona que no está en el archivo: solo los enemigos colocados en Blender
+    fn placed_only(name: &str) -> Self {
         Self {
-            timer: Timer::from_seconds(10.0, TimerMode::Repeating),
-            current_wave: 0,
-            current_zone: "first_arena".to_string(),
+            name: name.to_string(),
+            autostart: false,
+            rest: 0.0,
+            points: None,
+            waves: Vec::new(),
+            endless: None,
+        }
+    }
+
+    /// La `zone` de los `EnemySpawn` de los que salen sus enemigos
+    pub fn points(&self) -> &str {
+        self.points.as_deref().unwrap_or(&self.name)
+    }
+
+    /// La oleada `wave` (desde 1): la del archivo o, si se acabaron, una generada
+    fn wave(&self, wave: i32) -> WaveDef {
+        if let Some(def) = self.waves.get(wave as usize - 1) {
+            return def.clone();
+        }
+        match &self.endless {
+            Some(endless) => endless.generate(wave, wave - self.waves.len() as i32 - 1),
+            None => WaveDef::default(),
         }
     }
 }
 
-/// ===============================
-/// COMPONENTE: SPAWN DESDE BLENDER
-/// (Importado por bevy_skein)
-/// ===============================
+#[derive(Deserialize, Clone, Debug, Default)]
+pub struct WaveDef {
+    #[serde(default)]
+    pub enemies: Vec<WaveGroup>,
+    #[serde(default)]
+    pub clear: ClearCondition,
+}
 
+/// `count` enemigos de un tipo, uno cada `interval` segundos
+#[derive(Deserialize, Clone, Debug)]
+pub struct WaveGroup {
+    pub archetype: String,
+    pub count: u32,
+    /// Segundos desde el inicio de la oleada hasta el primero
+    #[serde(default)]
+    pub delay: f32,
+    #[serde(default)]
+    pub interval: f32,
+}
 
-/// Marca que ya fue usado
-// #[derive(Component)]
-// pub struct SpawnConsumed;
+/// Cómo crecen las oleadas de una zona sin fin
+#[derive(Deserialize, Clone, Debug)]
+pub struct EndlessWaves {
+    /// Tipos que pueden salir y con qué peso
+    pub enemies: Vec<EndlessEnemy>,
+    /// Enemigos de la primera oleada generada
+    pub count: u32,
+    /// Enemigos de más por cada oleada generada
+    pub count_per_wave: f32,
+    pub max_count: u32,
+    /// Segundos entre enemigo y enemigo en la primera oleada generada
+    pub interval: f32,
+    /// Lo que se multiplica `interval` en cada oleada (menos de 1 = más seguidos)
+    #[serde(default = "EndlessWaves::default_interval_decay")]
+    pub interval_decay: f32,
+    #[serde(default)]
+    pub min_interval: f32,
+}
 
-/// ===============================
-/// PLUGIN
-/// ===============================
+#[derive(Deserialize, Clone, Debug)]
+pub struct EndlessEnemy {
+    pub archetype: String,
+    #[serde(default = "EndlessEnemy::default_weight")]
+    pub weight: f32,
+    /// Primera oleada en la que puede salir (la de la zona, no la generada)
+    #[serde(default)]
+    pub from_wave: i32,
+}
 
-pub struct WaveSpawnPlugin;
+impl EndlessEnemy {
+    fn default_weight() -> f32 {
+        1.0
+    }
+}
 
-impl Plugin for WaveSpawnPlugin {
-    fn build(&self, app: &mut App) {
-        app.register_type::<EnemySpawn>();
+impl EndlessWaves {
+    fn default_interval_decay() -> f32 {
+        1.0
+    }
 
-        app.init_resource::<WaveManager>();
+    /// La oleada `wave` de la zona, que es la `generated`-ésima generada (desde 0)
+    fn generate(&self, wave: i32, generated: i32) -> WaveDef {
+        let count = (self.count + (self.count_per_wave * generated as f32) as u32).min(self.max_count);
+        let interval = (self.interval * self.interval_decay.powi(generated)).max(self.min_interval);
+        let eligible: Vec<_> = self.enemies.iter().filter(|enemy| enemy.from_wave <= wave).collect();
+        let total_weight: f32 = eligible.iter().map(|enemy| enemy.weight).sum();
+        if eligible.is_empty() || total_weight <= 0.0 {
+            warn!("⚠️ Ningún tipo puede salir en la oleada sin fin {wave}");
+            return WaveDef::default();
+        }
 
-        app.add_systems(
-            OnEnter(Screen::Gameplay),
-            setup_waves,
-        );
+        let mut rng = rand::rng();
+        let enemies = (0..count)
+            .map(|n| {
+                let mut pick = rng.random_range(0.0..total_weight);
+                let enemy = eligible
+                    .iter()
+                    .find(|enemy| {
+                        pick -= enemy.weight;
+                        pick < 0.0
+                    })
+                    .unwrap_or(&eligible[eligible.len() - 1]);
+                WaveGroup {
+                    archetype: enemy.archetype.clone(),
+                    count: 1,
+                    delay: interval * n as f32,
+                    interval: 0.0,
+                }
+            })
+            .collect();
+        WaveDef { enemies, clear: ClearCondition::AllDead }
+    }
+}
 
-        app.add_systems(
-            Update,
-            update_waves
-                .run_if(in_state(Screen::Gameplay)),
-        );
+/// Cuándo se da una oleada por superada
+#[derive(Deserialize, Clone, Copy, Debug, Default)]
+pub enum ClearCondition {
+    /// No queda ningún enemigo de la zona, ni por salir
+    #[default]
+    AllDead,
+    /// Aguantar tantos segundos; lo que quede vivo sigue peleando
+    Timer(f32),
+    /// Cumplir un objetivo del nivel
+    Objective(ObjectiveGoal),
+}
+
+// -----------------------------------------------
+// CARGA
+// -----------------------------------------------
+
+#[derive(Resource, Asset, Clone, Reflect)]
+#[reflect(Resource)]
+pub struct WaveTables {
+    #[dependency]
+    table: Handle<WaveTable>,
+}
+
+impl FromWorld for WaveTables {
+    fn from_world(world: &mut World) -> Self {
+        let assets = world.resource::<AssetServer>();
+        Self { table: assets.load(WAVES_FILE) }
     }
 }
 
-/// ===============================
-/// SETUP
-/// ===============================
+#[derive(Default, TypePath)]
+struct WaveTableLoader;
 
-fn setup_waves(
-    mut manager: ResMut<WaveManager>,
-) {
-    manager.current_wave = 0;
-    manager.timer.reset();
+#[derive(Debug)]
+pub enum WaveTableError {
+    Io(std::io::Error),
+    Ron(ron::error::SpannedError),
+}
 
-    info!("🌊 Wave system initialized");
+impl fmt::Display for WaveTableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            WaveTableError::Io(err) => write!(f, "no se pudieron leer las oleadas: {err}"),
+            WaveTableError::Ron(err) => write!(f, "oleadas mal escritas: {err}"),
+        }
+    }
 }
 
-/// ===============================
-/// SISTEMA PRINCIPAL
-/// ===============================
+impl std::error::Error for WaveTableError {}
 
-fn update_waves(
-    time: Res<Time>,
-    mut manager: ResMut<WaveManager>,
+impl From<std::io::Error> for WaveTableError {
+    fn from(err: std::io::Error) -> Self {
+        WaveTableError::Io(err)
+    }
+}
+
+impl From<ron::error::SpannedError> for WaveTableError {
+    fn from(err: ron::error::SpannedError) -> Self {
+        WaveTableError::Ron(err)
+    }
+}
+
+impl AssetLoader for WaveTableLoader {
+    type Asset = WaveTable;
+    type Settings = ();
+    type Error = WaveTableError;
+
+    async fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &(),
+        _load_context: &mut LoadContext<'_>,
+    ) -> Result<Self::Asset, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        Ok(ron::de::from_bytes(&bytes)?)
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["waves.ron"]
+    }
+}
+
+// -----------------------------------------------
+// DIRECTOR
+// -----------------------------------------------
+
+#[derive(Resource, Default)]
+pub struct WaveDirector {
+    /// Zonas pedidas con `start_zone`, en orden
+    requested: VecDeque<String>,
+    active: Option<ActiveZone>,
+    completed: Vec<String>,
+    /// No arranca las `autostart`: solo las que se pidan
+    manual_only: bool,
+}
+
+impl WaveDirector {
+    /// Pide arrancar una zona. Si ya hay otra en marcha, espera a que acabe.
+    pub fn start_zone(&mut self, zone: impl Into<String>) {
+        let zone = zone.into();
+        if !self.requested.contains(&zone) && self.current_zone() != Some(zone.as_str()) {
+            self.requested.push_back(zone);
+        }
+    }
+
+    pub fn current_zone(&self) -> Option<&str> {
+        self.active.as_ref().map(|active| active.def.name.as_str())
+    }
+
+    /// Oleada en curso de la zona activa (empiezan en 1; 0 si no hay ninguna)
+    pub fn current_wave(&self) -> i32 {
+        self.active.as_ref().map_or(0, |active| active.wave)
+    }
+
+    pub fn total_waves(&self) -> i32 {
+        self.active.as_ref().map_or(0, |active| active.total_waves)
+    }
+
+    /// La zona activa no se acaba nunca (`total_waves` no significa nada)
+    pub fn is_endless(&self) -> bool {
+        self.active.as_ref().is_some_and(|active| active.def.endless.is_some())
+    }
+
+    /// Deja de arrancar zonas `autostart` hasta la próxima partida
+    pub fn manual_only(&mut self) {
+        self.manual_only = true;
+    }
+
+    pub fn is_complete(&self, zone: &str) -> bool {
+        self.completed.iter().any(|done| done == zone)
+    }
+}
+
+struct ActiveZone {
+    def: ZoneDef,
+    /// Las del archivo o las que pidan los enemigos colocados, lo que sea más
+    total_waves: i32,
+    wave: i32,
+    state: WaveState,
+}
+
+enum WaveState {
+    /// Respiro antes de la siguiente oleada
+    Resting(Timer),
+    Running(RunningWave),
+}
+
+struct RunningWave {
+    clear: ClearCondition,
+    elapsed: f32,
+    pending: Vec<PendingSpawn>,
+    bosses_defeated: u32,
+}
+
+struct PendingSpawn {
+    at: f32,
+    archetype: String,
+    position: Vec3,
+    /// Nombre del empty, para el log
+    point: String,
+}
+
+/// Enemigo que salió de una oleada
+#[derive(Component, Clone, Debug)]
+pub struct WaveEnemy {
+    pub zone: String,
+    pub wave: i32,
+}
+
+pub(super) fn reset_director(mut director: ResMut<WaveDirector>) {
+    *director = WaveDirector::default();
+    info!("🌊 Director de oleadas listo");
+}
+
+/// Arranca la siguiente zona pedida o, si no hay, la siguiente automática
+fn start_zones(
+    mut director: ResMut<WaveDirector>,
+    tables: Option<Res<WaveTables>>,
+    table_assets: Res<Assets<WaveTable>>,
+    navmesh_done: Res<NavmeshDone>,
+    spawns: Query<&EnemySpawn>,
+    mut completed_writer: MessageWriter<ZoneCompletedEvent>,
+) {
+    if director.active.is_some() || !navmesh_done.0 {
+        return;
+    }
+    let table = tables.as_ref().and_then(|tables| table_assets.get(&tables.table));
+
+    let next = loop {
+        match director.requested.pop_front() {
+            Some(zone) if director.is_complete(&zone) => {
+                warn!("⚠️ La zona '{zone}' ya se completó, no se repite");
+            }
+            Some(zone) => break Some(zone),
+            None if director.manual_only => break None,
+            None => {
+                break table.and_then(|table| {
+                    table
+                        .zones
+                        .iter()
+                        .find(|zone| zone.autostart && !director.is_complete(&zone.name))
+                        .map(|zone| zone.name.clone())
+                });
+            }
+        }
+    };
+    let Some(name) = next else { return; };
+
+    let def = table
+        .and_then(|table| table.zone(&name))
+        .cloned()
+        .unwrap_or_else(|| ZoneDef::placed_only(&name));
+    let placed_waves = spawns.iter().filter(|s| s.zone == def.points()).map(|s| s.wave).max().unwrap_or(0);
+    let total_waves = if def.endless.is_some() {
+        info!("🌊 Zona '{name}': oleadas sin fin");
+        i32::MAX
+    } else {
+        let total_waves = (def.waves.len() as i32).max(placed_waves);
+        if total_waves == 0 {
+            // Sin nada que sacar se quedaría activa para siempre: se da por superada
+            warn!("⚠️ La zona '{name}' no tiene oleadas ni en el archivo ni en Blender");
+            director.completed.push(name.clone());
+            completed_writer.write(ZoneCompletedEvent { zone: name });
+            return;
+        }
+        info!("🌊 Zona '{name}': {total_waves} oleadas");
+        total_waves
+    };
+
+    director.active = Some(ActiveZone {
+        def,
+        total_waves,
+        wave: 0,
+        state: WaveState::Resting(Timer::from_seconds(0.0, TimerMode::Once)),
+    });
+}
+
+/// Empieza oleadas tras el respiro y saca a los enemigos cuando les toca
+fn tick_waves(
     mut commands: Commands,
-    // Eliminamos Without<SpawnConsumed> para que los puntos sean reutilizables
-    spawns: Query<(&Name, &GlobalTransform, Option<&EnemySpawn>)>,
+    time: Res<Time>,
+    mut director: ResMut<WaveDirector>,
+    spawns: Query<(&Name, &GlobalTransform, &EnemySpawn)>,
+    alive: Query<&EnemyArchetypeHandle, Or<(With<Enemy>, With<SpawnTelegraph>)>>,
+    archetypes: Res<EnemyArchetypes>,
+    archetype_assets: Res<Assets<EnemyArchetype>>,
+    mut started_writer: MessageWriter<WaveStartedEvent>,
 ) {
-    manager.timer.tick(time.delta());
+    let Some(active) = director.active.as_mut() else { return; };
 
-    if !manager.timer.just_finished() {
-        return;
+    if let WaveState::Resting(timer) = &mut active.state {
+        timer.tick(time.delta());
+        if !timer.is_finished() || active.wave >= active.total_waves {
+            return;
+        }
+        active.wave += 1;
+        let wave = plan_wave(&active.def, active.wave, &spawns);
+        let total = if active.def.endless.is_some() { "∞".to_string() } else { active.total_waves.to_string() };
+        info!(
+            "🌊 Zona '{}': empieza la ola {}/{} ({} enemigos)",
+            active.def.name,
+            active.wave,
+            total,
+            wave.pending.len(),
+        );
+        started_writer.write(WaveStartedEvent { zone: active.def.name.clone(), wave: active.wave });
+        active.state = WaveState::Running(wave);
     }
 
-    manager.current_wave += 1;
-    info!("🌊 Wave Check: Ola {}, Zona {}", manager.current_wave, manager.current_zone);
+    let WaveState::Running(wave) = &mut active.state else { return; };
+    wave.elapsed += time.delta_secs();
 
-    let mut count = 0;
-    for (name, transform, maybe_spawn) in &spawns {
-        // 1. Filtro por nombre (visto en Blender)
-        if !name.as_str().contains("EnemySpawn") {
+    // Vivos por tipo, para respetar `max_alive` de cada uno
+    let mut alive_by_type: HashMap<AssetId<EnemyArchetype>, usize> = HashMap::default();
+    for handle in &alive {
+        *alive_by_type.entry(handle.0.id()).or_default() += 1;
+    }
+
+    let mut i = 0;
+    while i < wave.pending.len() {
+        let spawn = &wave.pending[i];
+        if spawn.at > wave.elapsed {
+            i += 1;
             continue;
         }
-
-        // 2. Filtro por componente (si Skein lo carga correctamente)
-        // Si el componente existe, validamos zona. Si no existe, spawneamos por defecto.
-        if let Some(spawn) = maybe_spawn {
-            if spawn.zone != manager.current_zone {
+        match archetypes.get(&spawn.archetype, &archetype_assets) {
+            Ok((handle, found)) => {
+                let rules = &found.def.spawn;
+                if active.wave < rules.min_wave {
+                    warn!(
+                        "⚠️ '{}' no sale antes de la ola {} (spawn '{}' en la ola {})",
+                        found.def.name, rules.min_wave, spawn.point, active.wave,
+                    );
+                    wave.pending.swap_remove(i);
+                    continue;
+                }
+                // Lleno de este tipo: espera a que muera alguno
+                let alive = alive_by_type.entry(handle.id()).or_default();
+                if rules.max_alive.is_some_and(|max| *alive >= max) {
+                    i += 1;
+                    continue;
+                }
+                *alive += 1;
+            }
+            Err(err) => {
+                error!("Spawn '{}' ignorado: {err}", spawn.point);
+                wave.pending.swap_remove(i);
                 continue;
             }
-            // Si quieres que un Empty específico solo aparezca en una ola concreta:
-             if spawn.wave != manager.current_wave { continue; }
         }
 
-        let pos = transform.translation();
+        let spawn = wave.pending.swap_remove(i);
+        debug!("👾 Ola {}: '{}' desde '{}' en {:?}", active.wave, spawn.archetype, spawn.point, spawn.position);
+        commands.queue(TelegraphedSpawnCmd {
+            archetype: spawn.archetype,
+            position: spawn.position,
+            source: spawn.point,
+            wave: Some(WaveEnemy { zone: active.def.name.clone(), wave: active.wave }),
+        });
+    }
+}
 
-        // Evitamos el origen por errores de carga de escena
-        if pos == Vec3::ZERO { continue; }
+/// Qué sale en una oleada, de dónde y cuándo
+fn plan_wave(def: &ZoneDef, wave: i32, spawns: &Query<(&Name, &GlobalTransform, &EnemySpawn)>) -> RunningWave {
+    let wave_def = def.wave(wave);
+    let mut pending = Vec::new();
 
-        info!("👾 Ola {}: Spawning enemigo desde '{}' en {:?}", manager.current_wave, name, pos);
+    // Evitamos el origen por errores de carga de escena
+    let zone_spawns: Vec<_> = spawns
+        .iter()
+        .filter(|(_, transform, spawn)| spawn.zone == def.points() && transform.translation() != Vec3::ZERO)
+        .collect();
 
-        // Ejecutamos el comando de spawn
-        commands.queue(EnemySpawnCmd {
-            transform: Transform::from_translation(pos),
-            parent: None,
+    // Colocados a mano para esta oleada
+    for (name, transform, spawn) in zone_spawns.iter().filter(|(_, _, spawn)| spawn.wave == wave) {
+        pending.push(PendingSpawn {
+            at: spawn.delay,
+            archetype: spawn.r#type.clone(),
+            position: transform.translation(),
+            point: name.to_string(),
         });
+    }
 
-        count += 1;
+    // Los del archivo, por turnos entre los puntos de salida
+    let mut points: Vec<_> = zone_spawns.iter().filter(|(_, _, spawn)| spawn.wave == 0).collect();
+    if points.is_empty() {
+        points = zone_spawns.iter().collect();
+    }
+    let mut next_point = 0;
+    for group in &wave_def.enemies {
+        if points.is_empty() {
+            warn!("⚠️ No hay puntos de spawn para la zona: {}", def.name);
+            break;
+        }
+        for n in 0..group.count {
+            let (name, transform, spawn) = points[next_point % points.len()];
+            next_point += 1;
+            pending.push(PendingSpawn {
+                at: group.delay + group.interval * n as f32 + spawn.delay,
+                archetype: group.archetype.clone(),
+                position: transform.translation(),
+                point: name.to_string(),
+            });
+        }
     }
 
-    if count > 0 {
-        info!("🚀 Ola {} completada: {} enemigos generados", manager.current_wave, count);
-    } else {
-        warn!("⚠️ No se encontraron puntos de spawn válidos para la zona: {}", manager.current_zone);
+    RunningWave {
+        clear: wave_def.clear,
+        elapsed: 0.0,
+        pending,
+        bosses_defeated: 0,
+    }
+}
+
+fn check_wave_clear(
+    mut director: ResMut<WaveDirector>,
+    wave_enemies: Query<&WaveEnemy, Or<(With<Enemy>, With<SpawnTelegraph>)>>,
+    tracker: Res<CapsuleTracker>,
+    mut bosses: MessageReader<BossDefeatedEvent>,
+    mut cleared_writer: MessageWriter<WaveClearedEvent>,
+    mut completed_writer: MessageWriter<ZoneCompletedEvent>,
+) {
+    let bosses_defeated = bosses.read().count() as u32;
+    let Some(active) = director.active.as_mut() else { return; };
+    let WaveState::Running(wave) = &mut active.state else { return; };
+    wave.bosses_defeated += bosses_defeated;
+
+    let cleared = match wave.clear {
+        ClearCondition::AllDead => {
+            wave.pending.is_empty() && !wave_enemies.iter().any(|enemy| enemy.zone == active.def.name)
+        }
+        ClearCondition::Timer(seconds) => wave.elapsed >= seconds,
+        ClearCondition::Objective(ObjectiveGoal::BreakAllCapsules) => tracker.all_broken(),
+        ClearCondition::Objective(ObjectiveGoal::DefeatBoss) => wave.bosses_defeated > 0,
+    };
+    if !cleared {
+        return;
+    }
+
+    info!("✅ Zona '{}': ola {} superada", active.def.name, active.wave);
+    cleared_writer.write(WaveClearedEvent { zone: active.def.name.clone(), wave: active.wave });
+
+    if active.wave < active.total_waves {
+        active.state = WaveState::Resting(Timer::from_seconds(active.def.rest, TimerMode::Once));
+        return;
+    }
+
+    let zone = active.def.name.clone();
+    info!("🏁 Zona '{zone}' completada");
+    director.active = None;
+    director.completed.push(zone.clone());
+    completed_writer.write(ZoneCompletedEvent { zone });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn endless() -> EndlessWaves {
+        EndlessWaves {
+            enemies: vec![
+                EndlessEnemy { archetype: "hammerhead".to_string(), weight: 3.0, from_wave: 0 },
+                EndlessEnemy { archetype: "skyhammer".to_string(), weight: 1.0, from_wave: 3 },
+            ],
+            count: 4,
+            count_per_wave: 1.5,
+            max_count: 10,
+            interval: 1.2,
+            interval_decay: 0.5,
+            min_interval: 0.3,
+        }
+    }
+
+    fn count(wave: &WaveDef) -> u32 {
+        wave.enemies.iter().map(|group| group.count).sum()
+    }
+
+    #[test]
+    fn generated_waves_grow() {
+        let endless = endless();
+        assert_eq!(count(&endless.generate(1, 0)), 4);
+        assert_eq!(count(&endless.generate(2, 1)), 5);
+        assert_eq!(count(&endless.generate(3, 2)), 7);
+    }
+
+    #[test]
+    fn generated_waves_clamp_at_max_count() {
+        let endless = endless();
+        assert_eq!(count(&endless.generate(50, 49)), endless.max_count);
+    }
+
+    #[test]
+    fn generated_waves_clamp_at_min_interval() {
+        let endless = endless();
+        let wave = endless.generate(50, 49);
+        let delays: Vec<f32> = wave.enemies.iter().map(|group| group.delay).collect();
+        for pair in delays.windows(2) {
+            assert!((pair[1] - pair[0] - endless.min_interval).abs() < 1e-5);
+        }
+        assert!(matches!(wave.clear, ClearCondition::AllDead));
+    }
+
+    #[test]
+    fn generated_waves_respect_from_wave() {
+        let endless = endless();
+        for _ in 0..20 {
+            let wave = endless.generate(2, 1);
+            assert!(wave.enemies.iter().all(|group| group.archetype == "hammerhead"));
+        }
+    }
+
+    #[test]
+    fn zone_falls_back_to_generated_waves() {
+        let zone = ZoneDef {
+            waves: vec![WaveDef {
+                enemies: vec![WaveGroup { archetype: "hammerhead".to_string(), count: 2, delay: 0.0, interval: 0.0 }],
+                clear: ClearCondition::AllDead,
+            }],
+            endless: Some(endless()),
+            ..ZoneDef::placed_only("survival")
+        };
+        assert_eq!(count(&zone.wave(1)), 2);
+        // La 2 es la primera generada
+        assert_eq!(count(&zone.wave(2)), endless().count);
     }
 }
@@ -0,0 +1,257 @@
+//! Aviso antes de que salga un enemigo
+//!
+//! Los enemigos del director y los esbirros de los jefes no aparecen de
+//! golpe: primero se valida el sitio y luego sale una burbuja de goop que
+//! crece durante `TELEGRAPH_TIME`. Al reventar sale el enemigo; si el
+//! jugador la revienta antes de un golpe, no sale nada.
+//!
+//! La validación proyecta el punto sobre el navmesh (los voladores no lo
+//! necesitan) y comprueba con avian que la cápsula del enemigo no queda
+//! metida en la geometría. Si falla, se descarta y se dice en el log con el
+//! nombre del empty.
+
+use avian3d::prelude::*;
+use bevy::{pbr::ExtendedMaterial, prelude::*};
+use bevy_landmass::prelude::*;
+
+use crate::PausableSystems;
+use crate::screens::Screen;
+use crate::screens::gameplay::{
+    NavmeshArchipelagoHolder, NavmeshDone,
+    character_controller::AttackAction,
+    enemy::{EnemySpawnCmd, spawn_enemy},
+    enemy_archetype::{EnemyArchetypeHandle, EnemyArchetypes, EnemyArchetype, EnemyMovement},
+    particle_system::{ParticleAssets, ParticleBudget, spawn_death_burst},
+    player::Player,
+    spawn_enemy_waves::WaveEnemy,
+};
+use crate::visuals::goop::{GoopMaterial, GoopMaterialExtention};
+
+pub struct SpawnTelegraphPlugin;
+
+/// Segundos que dura la burbuja antes de soltar al enemigo
+const TELEGRAPH_TIME: f32 = 1.6;
+/// Radio de la burbuja al reventar, por metro de alto del enemigo
+const BUBBLE_SIZE: f32 = 0.6;
+/// Hasta dónde se busca navmesh alrededor del empty
+const NAVMESH_SEARCH: PointSampleDistance3d = PointSampleDistance3d {
+    horizontal_distance: 2.0,
+    distance_above: 2.0,
+    distance_below: 6.0,
+    vertical_preference_ratio: 2.0,
+};
+/// La cápsula de prueba es algo más pequeña que la real, para no chocar con
+/// el suelo en cuestas
+const OVERLAP_MARGIN: f32 = 0.9;
+
+impl Plugin for SpawnTelegraphPlugin {
+    fn build(&self, app: &mut App) {
+        // En `Startup`: `Assets<GoopMaterial>` lo registra `visuals`, que se
+        // añade después que las pantallas
+        app.add_systems(Startup, setup_telegraph_assets);
+        app.add_systems(
+            Update,
+            (pop_telegraphs, telegraph_tick)
+                .chain()
+                .in_set(PausableSystems)
+                .run_if(in_state(Screen::Gameplay)),
+        );
+    }
+}
+
+/// Enemigo por salir. Se valida el sitio al aplicarse el comando.
+pub struct TelegraphedSpawnCmd {
+    pub archetype: String,
+    /// Los pies del enemigo; se proyecta sobre el navmesh
+    pub position: Vec3,
+    /// Quién lo pide, para el log (el nombre del empty, por ejemplo)
+    pub source: String,
+    /// Si sale de una oleada del director
+    pub wave: Option<WaveEnemy>,
+}
+
+impl Command for TelegraphedSpawnCmd {
+    fn apply(self, world: &mut World) {
+        world.run_system_cached_with(start_telegraph, self).unwrap();
+    }
+}
+
+/// Burbuja de un enemigo que está a punto de salir
+#[derive(Component)]
+pub struct SpawnTelegraph {
+    archetype: String,
+    /// Donde van los pies del enemigo
+    position: Vec3,
+    source: String,
+    wave: Option<WaveEnemy>,
+    timer: Timer,
+    size: f32,
+}
+
+#[derive(Resource)]
+struct TelegraphAssets {
+    mesh: Handle<Mesh>,
+    /// Compartido: todas las burbujas laten a la vez
+    material: Handle<GoopMaterial>,
+}
+
+fn setup_telegraph_assets(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<GoopMaterial>>,
+) {
+    let material = materials.add(ExtendedMaterial {
+        base: StandardMaterial {
+            base_color: Color::srgba(0.2, 0.0, 0.25, 0.7),
+            emissive: LinearRgba::new(0.8, 0.1, 0.9, 1.0),
+            alpha_mode: AlphaMode::Blend,
+            ..default()
+        },
+        extension: GoopMaterialExtention::new(3.0, 0.2),
+    });
+    commands.insert_resource(TelegraphAssets {
+        mesh: meshes.add(Sphere { radius: 1.0 }),
+        material,
+    });
+}
+
+fn start_telegraph(
+    In(request): In<TelegraphedSpawnCmd>,
+    mut commands: Commands,
+    archetypes: Res<EnemyArchetypes>,
+    archetype_assets: Res<Assets<EnemyArchetype>>,
+    navmesh_done: Option<Res<NavmeshDone>>,
+    archipelago: Option<Res<NavmeshArchipelagoHolder>>,
+    archipelagos: Query<&Archipelago3d>,
+    spatial_query: SpatialQuery,
+    colliders: Query<&ColliderOf>,
+    bodies: Query<&RigidBody>,
+    assets: Res<TelegraphAssets>,
+) {
+    let (handle, archetype) = match archetypes.get(&request.archetype, &archetype_assets) {
+        Ok(found) => found,
+        Err(err) => {
+            error!("Spawn '{}' ignorado: {err}", request.source);
+            return;
+        }
+    };
+    let def = &archetype.def;
+
+    // Los de suelo, sobre el navmesh (si ya está)
+    let mut position = request.position;
+    let navmesh = archipelago
+        .filter(|_| navmesh_done.is_some_and(|done| done.0))
+        .and_then(|holder| archipelagos.get(holder.0).ok());
+    if let (EnemyMovement::Ground, Some(navmesh)) = (&def.movement, navmesh) {
+        match navmesh.sample_point(position, &NAVMESH_SEARCH) {
+            Ok(sampled) => position = sampled.point(),
+            Err(_) => {
+                warn!("⚠️ Spawn '{}' descartado: {} no está sobre el navmesh", request.source, position);
+                return;
+            }
+        }
+    }
+
+    // Que la cápsula no quede dentro de la geometría
+    let scale = def.scale * OVERLAP_MARGIN;
+    let center = position + Vec3::Y * (def.spawn.spawn_height + def.collider.height * def.scale);
+    let blocked = spatial_query
+        .shape_intersections(
+            &Collider::capsule(def.collider.radius * scale, def.collider.length * scale),
+            center,
+            Quaternion::default(),
+            &SpatialQueryFilter::default(),
+        )
+        .into_iter()
+        .map(|hit| colliders.get(hit).map_or(hit, |c| c.body))
+        .find(|body| bodies.get(*body).is_ok_and(|rb| rb.is_static()));
+    if let Some(body) = blocked {
+        warn!("⚠️ Spawn '{}' descartado: {} se mete en la geometría ({body})", request.source, center);
+        return;
+    }
+
+    let size = (def.collider.length + def.collider.radius * 2.0) * def.scale * BUBBLE_SIZE;
+    let mut telegraph = commands.spawn((
+        Name::new(format!("SpawnTelegraph_{}", request.source)),
+        SpawnTelegraph {
+            archetype: request.archetype,
+            position,
+            source: request.source,
+            wave: request.wave.clone(),
+            timer: Timer::from_seconds(TELEGRAPH_TIME, TimerMode::Once),
+            size,
+        },
+        // Cuenta como vivo para `max_alive` mientras no sale
+        EnemyArchetypeHandle(handle),
+        Mesh3d(assets.mesh.clone()),
+        MeshMaterial3d(assets.material.clone()),
+        Transform::from_translation(center).with_scale(Vec3::splat(size * 0.2)),
+        DespawnOnExit(Screen::Gameplay),
+    ));
+    // Cuenta para la oleada como un enemigo más
+    if let Some(wave) = request.wave {
+        telegraph.insert(wave);
+    }
+}
+
+/// Un golpe del jugador revienta las burbujas que tenga delante
+fn pop_telegraphs(
+    mut commands: Commands,
+    mut attacks: MessageReader<AttackAction>,
+    player: Single<&Transform, With<Player>>,
+    telegraphs: Query<(Entity, &Transform, &SpawnTelegraph)>,
+    particle_assets: Res<ParticleAssets>,
+    mut particle_budget: ResMut<ParticleBudget>,
+) {
+    const MIN_DOT_PRODUCT: f32 = 0.75;
+
+    for attack in attacks.read() {
+        let (forward, range) = attack.reach();
+        for (entity, transform, telegraph) in &telegraphs {
+            let to_bubble = transform.translation - player.translation;
+            let reach = range + transform.scale.x;
+            if to_bubble.length() > reach || forward.dot(to_bubble.normalize_or_zero()) < MIN_DOT_PRODUCT {
+                continue;
+            }
+            info!("💥 Spawn '{}' interrumpido", telegraph.source);
+            spawn_death_burst(&mut commands, &particle_assets, &mut particle_budget, transform.translation);
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+fn telegraph_tick(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut telegraphs: Query<(Entity, &mut SpawnTelegraph, &mut Transform)>,
+    mut materials: ResMut<Assets<GoopMaterial>>,
+    assets: Res<TelegraphAssets>,
+) {
+    if let Some(mat) = materials.get_mut(&assets.material) {
+        mat.extension.extent = 0.2 + 0.15 * (time.elapsed_secs() * 6.0).sin();
+    }
+
+    for (entity, mut telegraph, mut transform) in telegraphs.iter_mut() {
+        telegraph.timer.tick(time.delta());
+        let k = telegraph.timer.fraction();
+        transform.scale = Vec3::splat(telegraph.size * (0.2 + 0.8 * k * k));
+        if !telegraph.timer.is_finished() {
+            continue;
+        }
+
+        commands.entity(entity).despawn();
+        let spawn = EnemySpawnCmd {
+            transform: Transform::from_translation(telegraph.position),
+            parent: None,
+            archetype: std::mem::take(&mut telegraph.archetype),
+        };
+        let wave = telegraph.wave.take();
+        commands.queue(move |world: &mut World| {
+            if let Ok(Some(enemy)) = world.run_system_cached_with(spawn_enemy, spawn)
+                && let Some(wave) = wave
+            {
+                world.entity_mut(enemy).insert(wave);
+            }
+        });
+    }
+}
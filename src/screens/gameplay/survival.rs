@@ -0,0 +1,181 @@
+//! Modo supervivencia
+//!
+//! Se elige en el menú principal (`GameMode::Survival`). Usa el mismo nivel,
+//! pero en vez de romper cápsulas se juega la zona `survival` del director,
+//! que genera oleadas sin fin en los empties de la primera arena. No se gana:
+//! al morir se acaba la partida y se abre el menú de puntuación
+//! (`Menu::SurvivalOver`), que guarda la marca en el leaderboard local.
+//!
+//! La puntuación sale de tres sitios:
+//!
+//! - Cada muerte da `KILL_POINTS` por el multiplicador de la racha
+//!   (`KillStreak` del HUD): uno más cada `STREAK_STEP` muertes seguidas.
+//! - Cada oleada superada da `WAVE_BONUS` por su número.
+//! - El tiempo vivo da `POINTS_PER_SECOND`.
+
+use bevy::prelude::*;
+
+use crate::PausableSystems;
+use crate::screens::Screen;
+use crate::screens::gameplay::{
+    events::{EnemyKilledEvent, WaveClearedEvent, WaveStartedEvent},
+    hud::{KillStreak, track_kills},
+    player::Player,
+    spawn_enemy_waves::{WaveDirector, reset_director},
+};
+
+pub struct SurvivalPlugin;
+
+/// Zona del `.waves.ron` que se juega en supervivencia
+const SURVIVAL_ZONE: &str = "survival";
+const KILL_POINTS: u32 = 100;
+const STREAK_STEP: u32 = 5;
+const MAX_MULTIPLIER: u32 = 5;
+const WAVE_BONUS: u32 = 250;
+const POINTS_PER_SECOND: f32 = 5.0;
+
+impl Plugin for SurvivalPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameMode>();
+        app.init_resource::<SurvivalRun>();
+        app.add_systems(
+            OnEnter(Screen::Gameplay),
+            (start_survival.after(reset_director), spawn_survival_hud).run_if(survival_mode),
+        );
+        app.add_systems(
+            Update,
+            (
+                score_kills.after(track_kills),
+                score_waves,
+                score_time,
+                update_survival_hud,
+            )
+                .chain()
+                .run_if(in_state(Screen::Gameplay).and(survival_mode))
+                .in_set(PausableSystems),
+        );
+    }
+}
+
+/// Qué se juega al pulsar "Play" o "Survival" en el menú principal
+#[derive(Resource, Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum GameMode {
+    /// El nivel demo: romper todas las cápsulas (o derrotar al jefe)
+    #[default]
+    Story,
+    Survival,
+}
+
+pub fn survival_mode(mode: Res<GameMode>) -> bool {
+    *mode == GameMode::Survival
+}
+
+/// Cómo va la partida de supervivencia en curso (o cómo acabó la última)
+#[derive(Resource, Default, Clone, Debug)]
+pub struct SurvivalRun {
+    pub score: u32,
+    pub kills: u32,
+    pub best_streak: u32,
+    /// Última oleada empezada
+    pub wave: i32,
+    /// Segundos vivo
+    pub time: f32,
+    /// Puntos por tiempo aún sin sumar (se suman enteros)
+    time_points: f32,
+}
+
+impl SurvivalRun {
+    /// Multiplicador de los puntos por muerte con una racha de `streak`
+    pub fn multiplier(streak: u32) -> u32 {
+        (1 + streak / STREAK_STEP).min(MAX_MULTIPLIER)
+    }
+}
+
+fn start_survival(mut run: ResMut<SurvivalRun>, mut director: ResMut<WaveDirector>) {
+    *run = SurvivalRun::default();
+    director.manual_only();
+    director.start_zone(SURVIVAL_ZONE);
+    info!("🏆 Supervivencia: zona '{SURVIVAL_ZONE}'");
+}
+
+fn score_kills(
+    mut killed: MessageReader<EnemyKilledEvent>,
+    streak: Res<KillStreak>,
+    mut run: ResMut<SurvivalRun>,
+) {
+    let kills = killed.read().count() as u32;
+    if kills == 0 {
+        return;
+    }
+    // La racha ya cuenta las de este frame; cada una con el multiplicador que tocaba
+    for n in 0..kills {
+        let streak = streak.kills.saturating_sub(kills - 1 - n);
+        run.score += KILL_POINTS * SurvivalRun::multiplier(streak);
+    }
+    run.kills += kills;
+    run.best_streak = run.best_streak.max(streak.kills);
+}
+
+fn score_waves(
+    mut started: MessageReader<WaveStartedEvent>,
+    mut cleared: MessageReader<WaveClearedEvent>,
+    mut run: ResMut<SurvivalRun>,
+) {
+    for event in started.read().filter(|event| event.zone == SURVIVAL_ZONE) {
+        run.wave = event.wave;
+    }
+    for event in cleared.read().filter(|event| event.zone == SURVIVAL_ZONE) {
+        run.score += WAVE_BONUS * event.wave.max(0) as u32;
+    }
+}
+
+fn score_time(time: Res<Time>, player: Single<&Player>, mut run: ResMut<SurvivalRun>) {
+    if !player.is_alive() {
+        return;
+    }
+    run.time += time.delta_secs();
+    run.time_points += time.delta_secs() * POINTS_PER_SECOND;
+    let whole = run.time_points.floor();
+    run.time_points -= whole;
+    run.score += whole as u32;
+}
+
+// -----------------------------------------------
+// HUD
+// -----------------------------------------------
+
+/// Puntos, oleada y tiempo — arriba a la derecha
+#[derive(Component)]
+struct SurvivalHud;
+
+fn spawn_survival_hud(mut commands: Commands) {
+    commands.spawn((
+        Name::new("SurvivalHud"),
+        SurvivalHud,
+        Text::default(),
+        TextFont::from_font_size(22.0),
+        TextColor(Color::WHITE),
+        TextShadow::default(),
+        Node {
+            position_type: PositionType::Absolute,
+            top: Val::Px(20.0),
+            right: Val::Px(24.0),
+            ..default()
+        },
+        DespawnOnExit(Screen::Gameplay),
+    ));
+}
+
+fn update_survival_hud(
+    run: Res<SurvivalRun>,
+    streak: Res<KillStreak>,
+    mut hud: Single<&mut Text, With<SurvivalHud>>,
+) {
+    let seconds = run.time as u32;
+    let multiplier = SurvivalRun::multiplier(streak.kills);
+    let mut line = format!("{} pts\nOleada {} — {}:{:02}", run.score, run.wave, seconds / 60, seconds % 60);
+    if multiplier > 1 {
+        line.push_str(&format!("\nx{multiplier}"));
+    }
+    hud.0 = line;
+}
@@ -0,0 +1,183 @@
+//! Triggers de Blender
+//!
+//! Cualquier objeto con collider y un componente `Trigger` (Skein) se vuelve
+//! un sensor invisible que avisa con `TriggerEvent` cuando algo entra, sale
+//! o sigue dentro. Qué cuenta lo decide `filter` (solo el jugador, solo
+//! enemigos o cualquier cuerpo), y `once` lo apaga tras la primera entrada.
+//!
+//! El trigger no hace nada por sí mismo: cada sistema escucha los eventos
+//! que le interesan, por la `action` que lleva el trigger o por los
+//! componentes de su entidad. Así funcionan las arenas (`Arena` en la misma
+//! entidad) y los checkpoints (`action: "checkpoint"`).
+
+use avian3d::prelude::*;
+use bevy::prelude::*;
+
+use crate::screens::Screen;
+use crate::screens::gameplay::{enemy::Enemy, player::Player};
+
+pub struct TriggerPlugin;
+
+impl Plugin for TriggerPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<Trigger>();
+        app.add_message::<TriggerEvent>();
+        app.add_systems(
+            Update,
+            (setup_triggers, detect_triggers, trigger_stay)
+                .chain()
+                .run_if(in_state(Screen::Gameplay)),
+        );
+    }
+}
+
+#[derive(Component, Reflect, Default, Clone, Debug)]
+#[reflect(Component, Default)]
+pub struct Trigger {
+    /// Lo que dispara; los sistemas que escuchan filtran por esto
+    pub action: String,
+    pub filter: TriggerFilter,
+    /// Se apaga tras la primera entrada (ni salida ni `Stay` después)
+    pub once: bool,
+    /// Segundos entre eventos `Stay` mientras algo sigue dentro (0 = cada frame)
+    pub stay_interval: f32,
+}
+
+/// Quién dispara un trigger
+#[derive(Reflect, Default, Clone, Copy, PartialEq, Eq, Debug)]
+#[reflect(Default)]
+pub enum TriggerFilter {
+    #[default]
+    Player,
+    Enemies,
+    /// Cualquier cuerpo con `RigidBody` (cajas, loot, enemigos, jugador...)
+    Any,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum TriggerKind {
+    Enter,
+    Exit,
+    Stay,
+}
+
+/// Algo que pasa el filtro entró, salió o sigue dentro de un trigger
+#[derive(Message, Clone, Debug)]
+pub struct TriggerEvent {
+    pub trigger: Entity,
+    pub action: String,
+    pub kind: TriggerKind,
+    /// El cuerpo que lo disparó
+    pub body: Entity,
+}
+
+/// Cuerpos dentro del trigger, con cuántos de sus colliders lo tocan
+#[derive(Component, Default)]
+struct TriggerOccupants {
+    bodies: Vec<(Entity, u32)>,
+    stay_cooldown: f32,
+    /// Un `once` que ya se disparó
+    spent: bool,
+}
+
+fn setup_triggers(mut commands: Commands, triggers: Query<(Entity, &Trigger), Added<Trigger>>) {
+    for (entity, trigger) in &triggers {
+        debug!("Trigger {entity}: acción '{}' ({:?})", trigger.action, trigger.filter);
+        commands.entity(entity).insert((
+            TriggerOccupants::default(),
+            Sensor,
+            CollisionEventsEnabled,
+            Visibility::Hidden,
+        ));
+    }
+}
+
+fn detect_triggers(
+    mut started: MessageReader<CollisionStart>,
+    mut ended: MessageReader<CollisionEnd>,
+    mut triggers: Query<(&Trigger, &mut TriggerOccupants)>,
+    player: Query<(), With<Player>>,
+    enemies: Query<(), With<Enemy>>,
+    bodies: Query<(), With<RigidBody>>,
+    mut trigger_writer: MessageWriter<TriggerEvent>,
+) {
+    let passes = |filter: TriggerFilter, body: Entity| match filter {
+        TriggerFilter::Player => player.contains(body),
+        TriggerFilter::Enemies => enemies.contains(body),
+        TriggerFilter::Any => bodies.contains(body),
+    };
+    // Cada evento de colisión, visto desde el trigger (si lo hay)
+    let sides = |collider1: Entity, collider2: Entity, body1: Option<Entity>, body2: Option<Entity>| {
+        [(collider1, body2.unwrap_or(collider2)), (collider2, body1.unwrap_or(collider1))]
+    };
+
+    for ev in started.read() {
+        for (sensor, body) in sides(ev.collider1, ev.collider2, ev.body1, ev.body2) {
+            let Ok((trigger, mut occupants)) = triggers.get_mut(sensor) else { continue; };
+            if occupants.spent || !passes(trigger.filter, body) {
+                continue;
+            }
+            match occupants.bodies.iter_mut().find(|(entity, _)| *entity == body) {
+                Some((_, colliders)) => *colliders += 1,
+                None => {
+                    occupants.bodies.push((body, 1));
+                    occupants.spent = trigger.once;
+                    trigger_writer.write(TriggerEvent {
+                        trigger: sensor,
+                        action: trigger.action.clone(),
+                        kind: TriggerKind::Enter,
+                        body,
+                    });
+                }
+            }
+        }
+    }
+
+    for ev in ended.read() {
+        for (sensor, body) in sides(ev.collider1, ev.collider2, ev.body1, ev.body2) {
+            let Ok((trigger, mut occupants)) = triggers.get_mut(sensor) else { continue; };
+            let Some(i) = occupants.bodies.iter().position(|(entity, _)| *entity == body) else { continue; };
+            occupants.bodies[i].1 -= 1;
+            if occupants.bodies[i].1 > 0 {
+                continue;
+            }
+            occupants.bodies.swap_remove(i);
+            if !occupants.spent {
+                trigger_writer.write(TriggerEvent {
+                    trigger: sensor,
+                    action: trigger.action.clone(),
+                    kind: TriggerKind::Exit,
+                    body,
+                });
+            }
+        }
+    }
+}
+
+fn trigger_stay(
+    time: Res<Time>,
+    mut triggers: Query<(Entity, &Trigger, &mut TriggerOccupants)>,
+    alive: Query<(), With<GlobalTransform>>,
+    mut trigger_writer: MessageWriter<TriggerEvent>,
+) {
+    for (entity, trigger, mut occupants) in triggers.iter_mut() {
+        // Lo que se despawneó dentro ya no va a mandar `CollisionEnd`
+        occupants.bodies.retain(|(body, _)| alive.contains(*body));
+        if occupants.spent || occupants.bodies.is_empty() {
+            continue;
+        }
+        occupants.stay_cooldown -= time.delta_secs();
+        if occupants.stay_cooldown > 0.0 {
+            continue;
+        }
+        occupants.stay_cooldown = trigger.stay_interval;
+        for (body, _) in &occupants.bodies {
+            trigger_writer.write(TriggerEvent {
+                trigger: entity,
+                action: trigger.action.clone(),
+                kind: TriggerKind::Stay,
+                body: *body,
+            });
+        }
+    }
+}
@@ -0,0 +1,333 @@
+//! Inventario de armas y view model en la cámara
+//!
+//! El jugador empieza desarmado (solo patada). Cada arma es un `WeaponDef`
+//! con su escena, animaciones, daño y alcance; `Weapons` guarda las
+//! definiciones ya preparadas con su `AnimationGraph`. Cambiar el arma
+//! equipada en `WeaponInventory` reemplaza el modelo pegado a la cámara.
+
+use std::time::Duration;
+
+use bevy::{platform::collections::HashMap, prelude::*};
+
+use crate::screens::Screen;
+use crate::screens::gameplay::{
+    LevelAssets,
+    character_controller::AttackAction,
+    katana,
+    player::Player,
+    puppy::PuppyTransformation,
+};
+
+pub struct WeaponsPlugin;
+
+/// Segundos que tarda el view model en aparecer al equiparse
+const EQUIP_TIME: f32 = 0.2;
+
+impl Plugin for WeaponsPlugin {
+    fn build(&self, app: &mut App) {
+        app.register_type::<WeaponInventory>();
+        app.add_systems(OnEnter(Screen::Gameplay), setup_weapons);
+        app.add_systems(
+            Update,
+            (
+                swap_weapon_input,
+                sync_view_model,
+                setup_weapon_animations,
+                weapon_attack_input,
+                equip_scale_in,
+                #[cfg(feature = "dev")]
+                debug_give_katana,
+            )
+                .chain()
+                .in_set(crate::PausableSystems)
+                .run_if(in_state(Screen::Gameplay)),
+        );
+    }
+}
+
+// -----------------------------------------------
+// DEFINICIONES
+// -----------------------------------------------
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+pub enum WeaponId {
+    Katana,
+}
+
+/// Datos de un arma: todo lo necesario para equiparla y golpear con ella
+#[derive(Clone)]
+pub struct WeaponDef {
+    pub name: &'static str,
+    pub scene: Handle<Scene>,
+    pub idle: Handle<AnimationClip>,
+    pub swing: Handle<AnimationClip>,
+    pub swing_speed: f32,
+    pub damage: u32,
+    /// Poise que gasta cada golpe (ver `poise`)
+    pub poise_damage: f32,
+    pub range: f32,
+    /// Posición del modelo relativa a la cámara
+    pub view_transform: Transform,
+}
+
+pub struct LoadedWeapon {
+    pub def: WeaponDef,
+    graph: Handle<AnimationGraph>,
+    idle: AnimationNodeIndex,
+    swing: AnimationNodeIndex,
+}
+
+#[derive(Resource, Default)]
+pub struct Weapons(HashMap<WeaponId, LoadedWeapon>);
+
+impl Weapons {
+    pub fn get(&self, id: WeaponId) -> Option<&LoadedWeapon> {
+        self.0.get(&id)
+    }
+}
+
+fn setup_weapons(
+    mut commands: Commands,
+    mut graphs: ResMut<Assets<AnimationGraph>>,
+    level_assets: Res<LevelAssets>,
+) {
+    let defs = [(WeaponId::Katana, katana::katana(&level_assets))];
+
+    let mut weapons = Weapons::default();
+    for (id, def) in defs {
+        let (graph, nodes) = AnimationGraph::from_clips([def.idle.clone(), def.swing.clone()]);
+        weapons.0.insert(
+            id,
+            LoadedWeapon {
+                def,
+                graph: graphs.add(graph),
+                idle: nodes[0],
+                swing: nodes[1],
+            },
+        );
+    }
+    commands.insert_resource(weapons);
+}
+
+// -----------------------------------------------
+// INVENTARIO
+// -----------------------------------------------
+
+#[derive(Component, Default, Reflect)]
+#[reflect(Component)]
+pub struct WeaponInventory {
+    pub weapons: Vec<WeaponId>,
+    /// `None` = desarmado
+    pub equipped: Option<usize>,
+}
+
+impl WeaponInventory {
+    pub fn equipped(&self) -> Option<WeaponId> {
+        self.equipped.and_then(|i| self.weapons.get(i).copied())
+    }
+
+    /// Añade el arma (sin duplicados) y la equipa
+    pub fn give(&mut self, id: WeaponId) {
+        let index = match self.weapons.iter().position(|w| *w == id) {
+            Some(index) => index,
+            None => {
+                self.weapons.push(id);
+                self.weapons.len() - 1
+            }
+        };
+        self.equipped = Some(index);
+    }
+
+    pub fn cycle(&mut self) {
+        if self.weapons.is_empty() {
+            return;
+        }
+        self.equipped = Some(self.equipped.map_or(0, |i| (i + 1) % self.weapons.len()));
+    }
+
+    pub fn select(&mut self, index: usize) {
+        if index < self.weapons.len() {
+            self.equipped = Some(index);
+        }
+    }
+}
+
+/// Modelo del arma equipada, hijo de la cámara
+#[derive(Component)]
+pub struct WeaponViewModel {
+    pub weapon: WeaponId,
+    scale_in: f32,
+}
+
+/// Marca el `AnimationPlayer` dentro de un `WeaponViewModel`
+#[derive(Component)]
+pub struct WeaponAnimationPlayer {
+    pub weapon: WeaponId,
+}
+
+fn swap_weapon_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    gamepads: Query<&Gamepad>,
+    mut inventory: Single<&mut WeaponInventory, With<Player>>,
+    transforming: Query<(), With<PuppyTransformation>>,
+) {
+    if !transforming.is_empty() {
+        return;
+    }
+
+    if keyboard.just_pressed(KeyCode::KeyQ)
+        || gamepads.iter().any(|g| g.just_pressed(GamepadButton::North))
+    {
+        inventory.cycle();
+    }
+
+    const SLOTS: [KeyCode; 4] = [KeyCode::Digit1, KeyCode::Digit2, KeyCode::Digit3, KeyCode::Digit4];
+    for (index, key) in SLOTS.iter().enumerate() {
+        if keyboard.just_pressed(*key) {
+            inventory.select(index);
+        }
+    }
+}
+
+fn sync_view_model(
+    mut commands: Commands,
+    inventory: Single<&WeaponInventory, (With<Player>, Changed<WeaponInventory>)>,
+    view_models: Query<(Entity, &WeaponViewModel)>,
+    weapons: Res<Weapons>,
+    camera: Single<Entity, With<Camera3d>>,
+) {
+    let wanted = inventory.equipped();
+
+    for (entity, view_model) in view_models.iter() {
+        if Some(view_model.weapon) == wanted {
+            return;
+        }
+        commands.entity(entity).despawn();
+    }
+
+    let Some(id) = wanted else { return; };
+    let Some(weapon) = weapons.get(id) else { return; };
+
+    commands.spawn((
+        Name::new(weapon.def.name),
+        WeaponViewModel { weapon: id, scale_in: 0.0 },
+        SceneRoot(weapon.def.scene.clone()),
+        weapon.def.view_transform.with_scale(Vec3::ZERO),
+        ChildOf(*camera),
+    ));
+    info!("Arma equipada: {}", weapon.def.name);
+}
+
+// An `AnimationPlayer` is automatically added to the scene when it's ready.
+// When the player is added, start the idle animation of its weapon.
+fn setup_weapon_animations(
+    mut commands: Commands,
+    mut players: Query<(Entity, &mut AnimationPlayer), Added<AnimationPlayer>>,
+    parents: Query<&ChildOf>,
+    view_models: Query<&WeaponViewModel>,
+    weapons: Res<Weapons>,
+) {
+    for (entity, mut player) in &mut players {
+        let Some(view_model) = parents
+            .iter_ancestors(entity)
+            .find_map(|ancestor| view_models.get(ancestor).ok())
+        else {
+            continue;
+        };
+        let Some(weapon) = weapons.get(view_model.weapon) else { continue; };
+
+        let mut transitions = AnimationTransitions::new();
+
+        // Make sure to start the animation via the `AnimationTransitions`
+        // component. The `AnimationTransitions` component wants to manage all
+        // the animations and will get confused if the animations are started
+        // directly via the `AnimationPlayer`.
+        transitions
+            .play(&mut player, weapon.idle, Duration::ZERO)
+            .repeat();
+
+        commands.entity(entity).insert((
+            AnimationGraphHandle(weapon.graph.clone()),
+            transitions,
+            WeaponAnimationPlayer { weapon: view_model.weapon },
+        ));
+    }
+}
+
+fn weapon_attack_input(
+    mouse_input: Res<ButtonInput<MouseButton>>,
+    player: Single<(&WeaponInventory, &Transform), With<Player>>,
+    mut animation_players: Query<(
+        &WeaponAnimationPlayer,
+        &mut AnimationPlayer,
+        &mut AnimationTransitions,
+    )>,
+    transforming: Query<(), With<PuppyTransformation>>,
+    weapons: Res<Weapons>,
+    mut attack_writer: MessageWriter<AttackAction>,
+) {
+    let (inventory, player_transform) = *player;
+    let equipped = inventory.equipped().and_then(|id| weapons.get(id).map(|w| (id, w)));
+
+    if mouse_input.just_pressed(MouseButton::Left) && transforming.is_empty() {
+        match equipped {
+            Some((_, weapon)) => {
+                attack_writer.write(AttackAction::Weapon {
+                    forward: player_transform.forward(),
+                    damage: weapon.def.damage,
+                    poise_damage: weapon.def.poise_damage,
+                    range: weapon.def.range,
+                });
+            }
+            // Desarmado: el click izquierdo es una patada
+            None => {
+                attack_writer.write(AttackAction::Punch(player_transform.forward()));
+            }
+        }
+    }
+
+    for (anim, mut player, mut transitions) in &mut animation_players {
+        let Some(weapon) = weapons.get(anim.weapon) else { continue; };
+
+        if mouse_input.just_pressed(MouseButton::Left)
+            && transforming.is_empty()
+            && equipped.is_some_and(|(id, _)| id == anim.weapon)
+        {
+            transitions
+                .play(&mut player, weapon.swing, Duration::from_millis(60))
+                .set_speed(weapon.def.swing_speed);
+        }
+
+        if player.all_finished() {
+            transitions
+                .play(&mut player, weapon.idle, Duration::from_millis(250))
+                .repeat();
+        }
+    }
+}
+
+fn equip_scale_in(
+    time: Res<Time>,
+    weapons: Res<Weapons>,
+    mut view_models: Query<(&mut WeaponViewModel, &mut Transform)>,
+) {
+    for (mut view_model, mut transform) in view_models.iter_mut() {
+        if view_model.scale_in >= 1.0 {
+            continue;
+        }
+        let Some(weapon) = weapons.get(view_model.weapon) else { continue; };
+        view_model.scale_in = (view_model.scale_in + time.delta_secs() / EQUIP_TIME).min(1.0);
+        transform.scale = weapon.def.view_transform.scale * view_model.scale_in;
+    }
+}
+
+#[cfg(feature = "dev")]
+fn debug_give_katana(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut inventory: Single<&mut WeaponInventory, With<Player>>,
+) {
+    if keyboard.just_pressed(KeyCode::KeyK) {
+        inventory.give(WeaponId::Katana);
+        info!("DEBUG: Katana entregada!");
+    }
+}
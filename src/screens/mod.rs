@@ -5,6 +5,10 @@ mod loading;
 mod splash;
 mod title;
 
+#[cfg(feature = "dev")]
+pub(crate) use gameplay::enemy_bench;
+pub use gameplay::{GameMode, SurvivalRun};
+
 use bevy::{
     prelude::*,
     window::{CursorGrabMode, CursorOptions},